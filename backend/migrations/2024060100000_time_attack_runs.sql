@@ -0,0 +1,12 @@
+-- Finished solo time-attack runs, used to look up personal bests
+CREATE TABLE time_attack_runs (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    level_set VARCHAR(64) NOT NULL,
+    content_mode VARCHAR(32) NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    score INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_time_attack_runs_best ON time_attack_runs(user_id, level_set, content_mode, duration_seconds, score DESC);
@@ -0,0 +1,11 @@
+-- One scored daily-challenge run per user per day
+CREATE TABLE daily_challenge_runs (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    challenge_date DATE NOT NULL,
+    score INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, challenge_date)
+);
+
+CREATE INDEX idx_daily_challenge_runs_leaderboard ON daily_challenge_runs(challenge_date, score DESC);
@@ -0,0 +1,18 @@
+-- Prompts a user missed or skipped, scheduled for spaced-repetition review
+CREATE TABLE review_items (
+    id BIGSERIAL PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    prompt JSONB NOT NULL,
+    -- What the prompt asks about (see `review::prompt_key`); answer lists aren't built in a stable order
+    prompt_key TEXT NOT NULL,
+    ease_factor DOUBLE PRECISION NOT NULL DEFAULT 2.5,
+    interval_days INTEGER NOT NULL DEFAULT 0,
+    repetitions INTEGER NOT NULL DEFAULT 0,
+    lapses INTEGER NOT NULL DEFAULT 0,
+    due_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, prompt_key)
+);
+
+CREATE INDEX idx_review_items_due ON review_items(user_id, due_at);
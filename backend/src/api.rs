@@ -1,9 +1,12 @@
 use crate::{
-    utils::{generate_lobby_id, generate_player_id},
+    utils::{generate_lobby_id, generate_player_id, is_kanji},
+    data::DictEntry,
     models::{
         user::User,
         game::{GameAction, GameSession},
         GlobalStats,
+        DailyChallengeRun,
+        ReviewItem,
     },
     error::AppError,
     state::AppState,
@@ -34,6 +37,11 @@ use std::{
     time::{SystemTime, UNIX_EPOCH}
 };
 
+/// How many places the daily leaderboard shows.
+const DAILY_LEADERBOARD_SIZE: i64 = 20;
+/// Most words a single kanji lookup returns
+const KANJI_WORDS_LIMIT: usize = 50;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
     sub: String,
@@ -59,6 +67,22 @@ fn generate_jwt(user_id: &str) -> Result<String, ServerFnError> {
     ).map_err(|e| ServerFnError::new(e.to_string()))
 }
 
+fn decode_jwt(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "INSECURE_DEFAULT_SECRET".to_string());
+
+    jsonwebtoken::decode::<Claims>(
+        token,
+        &jsonwebtoken::DecodingKey::from_secret(secret.as_ref()),
+        &jsonwebtoken::Validation::default()
+    ).map(|data| data.claims)
+}
+
+/// The account a session token belongs to
+fn token_user_id(token: &str) -> Result<uuid::Uuid, ServerFnError> {
+    let claims = decode_jwt(token).map_err(|_| ServerFnError::new("Invalid or expired session"))?;
+    uuid::Uuid::parse_str(&claims.sub).map_err(|_| ServerFnError::new("Invalid or expired session"))
+}
+
 fn validate_username(username: &str) -> std::result::Result<(), ServerFnError> {
     if username.len() < 3 || username.len() > 20 {
         return Err(ServerFnError::new("Username must be between 3 and 20 characters"));
@@ -89,9 +113,7 @@ impl ApiContext for AppState {
         };
 
         let lobby_state = Arc::new(LobbyState::new(
-            Arc::clone(&self.kanji_data),
-            Arc::clone(&self.word_data),
-            Arc::clone(&self.dict_data),
+            &self.game_data,
             game_session_id,
             self.db_pool.read().await.clone()
         ));
@@ -120,7 +142,15 @@ impl ApiContext for AppState {
 
     async fn start_game(&self, lobby_id: LobbyId, request: StartGameRequest) -> JsonResult {
         let lobby = self.get_lobby(&lobby_id)?;
-        lobby.start_game(&request.player_id)?;
+        if lobby.settings.read(|s| s.mode) == shared::GameMode::Tournament {
+            self.start_tournament(&lobby_id, &lobby, &request.player_id).await?;
+            return Ok(json!({ "message": "Tournament started successfully" }));
+        }
+        if lobby.settings.read(|s| s.mode) == shared::GameMode::Review {
+            self.start_review(&lobby, &request.player_id).await?;
+        } else {
+            lobby.start_game(&request.player_id)?;
+        }
 
         let pool_guard = self.db_pool.read().await;
         if let Some(pool) = pool_guard.as_ref() {
@@ -311,7 +341,7 @@ impl ApiContext for AppState {
 
         lobby.remove_player(&player_id);
 
-        let is_empty = lobby.players.read(|players| players.is_empty());
+        let is_empty = lobby.has_no_humans();
         let actually_removed = if is_empty {
             self.lobbies.write(|lobbies| {
                 lobbies.remove(&lobby_id).is_some()
@@ -369,6 +399,11 @@ impl ApiContext for AppState {
                     return;
                 }
 
+                // Players are away in their match lobbies; the tournament cleans up after itself
+                if lobby_ref.bracket.read(|b| b.as_ref().is_some_and(|b| b.is_running() && b.tournament_id == lid)) {
+                    return;
+                }
+
                 tracing::info!("Lobby {} inactive for 60s with all players disconnected, cleaning up", lid.0);
                 lobbies.write(|l| { l.remove(&lid); });
 
@@ -387,7 +422,7 @@ impl ApiContext for AppState {
         let lobby = self.get_lobby(&lobby_id)?;
         lobby.kick_player(&requestor_id, &target_player_id)?;
 
-        let is_empty = lobby.players.read(|players| players.is_empty());
+        let is_empty = lobby.has_no_humans();
         if is_empty {
             self.lobbies.write(|lobbies| { lobbies.remove(&lobby_id); });
         }
@@ -401,6 +436,13 @@ impl ApiContext for AppState {
         Ok(json!({ "message": "Leader promoted" }))
     }
 
+    async fn add_bot(&self, lobby_id: LobbyId, requestor_id: PlayerId, profile: shared::BotProfile) -> JsonResult {
+        let lobby = self.get_lobby(&lobby_id)?;
+        let player_id = lobby.add_bot(&requestor_id, profile)?;
+        crate::bot::spawn_bot(&lobby, player_id.clone(), profile);
+        Ok(json!({ "message": "Bot added", "player_id": player_id }))
+    }
+
     async fn get_public_lobbies(&self) -> Result<Vec<shared::LobbySummary>, leptos::server_fn::error::ServerFnError> {
         let mut summaries = Vec::new();
         
@@ -425,6 +467,75 @@ impl ApiContext for AppState {
         
         Ok(summaries)
     }
+
+    async fn get_daily_leaderboard(&self) -> Result<shared::DailyLeaderboard, ServerFnError> {
+        let pool_guard = self.db_pool.read().await;
+        let db_pool = pool_guard.as_ref()
+            .ok_or_else(|| ServerFnError::new("Database not configured"))?;
+
+        let date = chrono::Utc::now().date_naive();
+        let standings = DailyChallengeRun::leaderboard(db_pool, date, DAILY_LEADERBOARD_SIZE).await?;
+
+        // Tied scores share a rank
+        let mut entries: Vec<shared::DailyLeaderboardEntry> = Vec::with_capacity(standings.len());
+        for (idx, standing) in standings.into_iter().enumerate() {
+            let score = standing.score.max(0) as u32;
+            let rank = match entries.last() {
+                Some(prev) if prev.score == score => prev.rank,
+                _ => idx as u32 + 1,
+            };
+            entries.push(shared::DailyLeaderboardEntry { rank, username: standing.username, score });
+        }
+
+        Ok(shared::DailyLeaderboard {
+            date: date.to_string(),
+            entries,
+        })
+    }
+
+    async fn get_review_queue(&self, token: String) -> Result<Vec<shared::ReviewQueueEntry>, ServerFnError> {
+        let user_id = token_user_id(&token)?;
+        let pool_guard = self.db_pool.read().await;
+        let db_pool = pool_guard.as_ref()
+            .ok_or_else(|| ServerFnError::new("Database not configured"))?;
+
+        let now = chrono::Utc::now();
+        let items = ReviewItem::list(db_pool, user_id).await?;
+        Ok(items.into_iter().map(|item| shared::ReviewQueueEntry {
+            prompt: item.prompt.display_text(),
+            due_at: item.due_at.to_rfc3339(),
+            interval_days: item.interval_days.max(0) as u32,
+            repetitions: item.repetitions.max(0) as u32,
+            lapses: item.lapses.max(0) as u32,
+            is_due: item.due_at <= now,
+        }).collect())
+    }
+
+    async fn reset_review_queue(&self, token: String) -> JsonResult {
+        let user_id = token_user_id(&token)?;
+        let pool_guard = self.db_pool.read().await;
+        let db_pool = pool_guard.as_ref()
+            .ok_or_else(|| ServerFnError::new("Database not configured"))?;
+
+        let removed = ReviewItem::reset(db_pool, user_id).await?;
+        Ok(json!({ "message": "Review queue cleared", "removed": removed }))
+    }
+
+    async fn lookup_kanji_words(&self, kanji: String, limit: usize) -> Result<Vec<shared::WordInfo>, ServerFnError> {
+        let mut chars = kanji.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return Err(ServerFnError::new("Expected a single kanji"));
+        };
+        if !is_kanji(c) {
+            return Err(ServerFnError::new("Expected a single kanji"));
+        }
+
+        Ok(self.game_data.dictionary.words_with_kanji(c)
+            .filter(|e| !e.filtered)
+            .take(limit.min(KANJI_WORDS_LIMIT))
+            .map(DictEntry::info)
+            .collect())
+    }
 }
 
 #[derive(Deserialize)]
@@ -439,20 +550,14 @@ pub async fn ws_handler(
     Query(params): Query<WsParams>,
     State(app_state): State<Arc<AppState>>,
 ) -> impl IntoResponse {
-    let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "INSECURE_DEFAULT_SECRET".to_string());
-
     let result = if let Some(t) = params.token {
-        jsonwebtoken::decode::<Claims>(
-            &t,
-            &jsonwebtoken::DecodingKey::from_secret(secret.as_ref()),
-            &jsonwebtoken::Validation::default()
-        )
+        decode_jwt(&t)
     } else {
         Err(jsonwebtoken::errors::ErrorKind::InvalidToken.into())
     };
 
     let claims = match result {
-        Ok(token_data) => token_data.claims,
+        Ok(claims) => claims,
         Err(e) => {
             let reason = match e.kind() {
                 jsonwebtoken::errors::ErrorKind::ExpiredSignature => "Token expired",
@@ -492,6 +597,7 @@ async fn handle_socket(socket: WebSocket, app_state: Arc<AppState>, lobby_id: Lo
         }
     };
 
+    lobby.set_player_account(&player_id, user_db_uuid);
     let mut rx = lobby.tx.subscribe();
 
     {
@@ -509,6 +615,8 @@ async fn handle_socket(socket: WebSocket, app_state: Arc<AppState>, lobby_id: Lo
             status,
             scores,
             timer_expires_at: lobby.timer_expires_at.read(|t| *t),
+            tier: lobby.get_current_tier(),
+            kanji: lobby.get_current_prompt_kanji(),
         }).unwrap_or_default();
         let _ = sender.send(Message::Text(game_msg.into())).await;
     }
@@ -0,0 +1,155 @@
+use rand::{RngExt, seq::IndexedRandom};
+use shared::{ActivePrompt, BotProfile, GameStatus, PlayerId};
+use std::sync::{Arc, Weak};
+use tokio::sync::broadcast;
+use crate::{
+    lobby::LobbyState,
+    utils::{ends_with_n, normalize_kanji_reading, shiritori_reading},
+};
+
+/// The quickest a bot ever answers, whatever its profile says.
+const MIN_THINK_MS: u32 = 300;
+
+/// Starts playing as the bot `player_id` in `lobby`.
+/// The task ends once the bot is removed from the lobby or the lobby is closed.
+pub fn spawn_bot(lobby: &Arc<LobbyState>, player_id: PlayerId, profile: BotProfile) {
+    // Subscribe straight away so a game starting right after isn't missed
+    let rx = lobby.tx.subscribe();
+    let bot = Bot { id: player_id, profile };
+    let lobby = Arc::downgrade(lobby);
+    tokio::spawn(async move {
+        bot.run(lobby, rx).await;
+    });
+}
+
+/// The prompt and turn a bot is thinking about; if either moves on, its answer is stale.
+type TurnKey = (u64, usize);
+
+struct Bot {
+    id: PlayerId,
+    profile: BotProfile,
+}
+
+impl Bot {
+    async fn run(self, lobby: Weak<LobbyState>, mut rx: broadcast::Receiver<String>) {
+        // Any message may mean a new prompt or a new turn; a lagged receiver just checks sooner
+        while let Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) = rx.recv().await {
+            loop {
+                // Our own answers come back as messages too; they are covered by checking again below
+                while let Ok(_) | Err(broadcast::error::TryRecvError::Lagged(_)) = rx.try_recv() {}
+
+                let key = match lobby.upgrade() {
+                    Some(lobby) if lobby.players.read(|ps| ps.iter().any(|p| p.id == self.id)) => self.turn_key(&lobby),
+                    _ => return,
+                };
+                let Some(key) = key else { break };
+
+                tokio::time::sleep(self.think_time()).await;
+
+                let Some(lobby) = lobby.upgrade() else { return };
+                if self.turn_key(&lobby) == Some(key) {
+                    self.act(&lobby);
+                }
+            }
+        }
+    }
+
+    fn think_time(&self) -> tokio::time::Duration {
+        let min = self.profile.min_delay_ms.max(MIN_THINK_MS);
+        let max = self.profile.max_delay_ms.max(min);
+        tokio::time::Duration::from_millis(rand::rng().random_range(min..=max) as u64)
+    }
+
+    /// What the bot would be answering right now, or `None` while it has nothing to do.
+    fn turn_key(&self, lobby: &LobbyState) -> Option<TurnKey> {
+        if lobby.game_status.read(|s| *s) != GameStatus::Playing {
+            return None;
+        }
+        let active = lobby.players.read(|ps| ps.iter().any(|p| p.id == self.id && !p.is_eliminated && !p.is_spectator));
+        if !active {
+            return None;
+        }
+
+        if lobby.settings.read(|s| s.mode.is_turn_based()) {
+            if lobby.get_current_turn_player().as_ref() != Some(&self.id) {
+                return None;
+            }
+        } else if lobby.skip_votes.read(|v| v.contains(&self.id)) {
+            return None; // Already voted to move on from this prompt
+        }
+
+        Some((lobby.prompt_counter.read(|c| *c), lobby.current_turn_index.read(|i| *i)))
+    }
+
+    fn act(&self, lobby: &LobbyState) {
+        let turn_based = lobby.settings.read(|s| s.mode.is_turn_based());
+
+        // Go along with a skip vote so a bot never holds one up
+        if !turn_based && lobby.skip_votes.read(|v| !v.is_empty()) {
+            let _ = lobby.process_skip(&self.id);
+            return;
+        }
+
+        let Some(prompt) = lobby.current_prompt.read(|p| p.clone()) else { return };
+        let mut rng = rand::rng();
+        let answer = if rng.random_range(0..100) < self.profile.accuracy {
+            pick_answer(lobby, &prompt, self.profile.common_bias, &mut rng)
+        } else {
+            lobby.dict_list.entry_at(rng.random_range(0..lobby.dict_list.len().max(1))).map(|e| e.word.clone())
+        };
+
+        // Without an answer a bot gives up on the prompt, just like a player pressing skip
+        let result = match answer {
+            Some(answer) => lobby.process_guess(&self.id, &answer),
+            None => lobby.process_skip(&self.id),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Bot {} failed to answer: {:?}", self.id.0, e);
+        }
+    }
+}
+
+/// A correct answer to `prompt`, if the bot can find one.
+/// `common_bias` percent of the time it keeps to words from the JLPT lists when any fit.
+pub fn pick_answer(lobby: &LobbyState, prompt: &ActivePrompt, common_bias: u8, rng: &mut impl rand::Rng) -> Option<String> {
+    let common_only = rng.random_range(0..100) < common_bias;
+
+    match prompt {
+        ActivePrompt::Kanji { character } => {
+            let kanji = character.chars().next()?;
+            let candidates: Vec<&String> = lobby.dict_list.words_with_kanji(kanji).map(|e| &e.word).collect();
+            choose_word(lobby, candidates, common_only, rng)
+        },
+        ActivePrompt::Compound { characters } => {
+            let kanji: Vec<char> = characters.iter().filter_map(|c| c.chars().next()).collect();
+            let candidates: Vec<&String> = lobby.dict_list.words_with_all_kanji(&kanji).map(|e| &e.word).collect();
+            choose_word(lobby, candidates, common_only, rng)
+        },
+        ActivePrompt::KanjiReading { readings, .. } => readings.choose(rng).map(|r| normalize_kanji_reading(r)),
+        ActivePrompt::Vocab { readings, .. } => readings.choose(rng).cloned(),
+        ActivePrompt::ReverseVocab { words, .. } => choose_word(lobby, words.iter().collect(), common_only, rng),
+        ActivePrompt::Meaning { word, .. } => Some(word.clone()),
+        ActivePrompt::Shiritori { reading, .. } => {
+            let used = lobby.used_words.read(|w| w.clone());
+            let candidates: Vec<&String> = lobby.reading_list.keys()
+                .filter(|w| !used.contains(*w))
+                .filter(|w| shiritori_reading(reading, w, &lobby.reading_list).is_some_and(|r| !ends_with_n(r)))
+                .collect();
+            choose_word(lobby, candidates, common_only, rng)
+        },
+    }
+}
+
+fn choose_word(lobby: &LobbyState, candidates: Vec<&String>, common_only: bool, rng: &mut impl rand::Rng) -> Option<String> {
+    // Words the lobby's dictionary setting leaves out would only be marked wrong
+    let full_dictionary = lobby.settings.read(|s| s.full_dictionary);
+    let candidates: Vec<&String> = candidates.into_iter()
+        .filter(|w| full_dictionary || !lobby.dict_list.get(w).is_some_and(|e| e.filtered))
+        .collect();
+    let common: Vec<&String> = candidates.iter()
+        .copied()
+        .filter(|w| lobby.word_list.iter().any(|level| level.contains_key(*w)))
+        .collect();
+    let pool = if common_only && !common.is_empty() { common } else { candidates };
+    pool.choose(rng).map(|w| w.to_string())
+}
@@ -1,31 +1,342 @@
-use crate::error::DataLoadError;
-use csv::{Reader, StringRecord};
-use std::{fs::{read_to_string, File}, path::Path, collections::{HashMap, HashSet}};
+use crate::{error::DataLoadError, utils::{is_kana, is_kanji}};
+use csv::{Reader, ReaderBuilder, StringRecord};
+use quick_xml::{escape::resolve_predefined_entity, events::Event, Reader as XmlReader};
+use std::{fs::{read_to_string, File}, path::Path, collections::{HashMap, HashSet}, sync::Arc};
 
 
 pub type KanjiData = Vec<Vec<Kanji>>;
-pub type DictData = HashSet<String>;
+pub type DictData = WordIndex;
 pub type JlptWordData = Vec<HashMap<String, Vec<String>>>;
+pub type ReadingData = HashMap<String, Vec<String>>;
+pub type SpellingData = Vec<HashMap<String, Vec<String>>>;
+pub type GlossData = HashMap<String, Vec<String>>;
+/// Frequency rank per word, 1 being the most common
+pub type WordRankData = HashMap<String, u32>;
 
-#[derive(Clone, PartialEq, Debug)]
+/// Read-only word data loaded once at startup and shared by every lobby.
+#[derive(Clone)]
+pub struct GameData {
+    /// One list per difficulty set, in `shared::DifficultySet::ALL` order
+    pub kanji: Arc<KanjiData>,
+    /// Kanji with too few dictionary words to be prompted, set by set like `kanji`
+    pub hard_kanji: Arc<KanjiData>,
+    pub words: Arc<JlptWordData>,
+    pub dictionary: Arc<DictData>,
+    pub readings: Arc<ReadingData>,
+    pub spellings: Arc<SpellingData>,
+    pub glosses: Arc<GlossData>,
+    pub word_ranks: Arc<WordRankData>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
 pub struct Kanji{
     pub kanji: String,
     pub frequency: i32,
+    pub readings: KanjiReadings,
+    pub details: KanjiDetails,
+}
+
+impl Kanji {
+    pub fn info(&self) -> shared::KanjiInfo {
+        shared::KanjiInfo {
+            character: self.kanji.clone(),
+            meanings: self.details.meanings.clone(),
+            on_readings: self.readings.on.clone(),
+            kun_readings: self.readings.kun.clone(),
+            stroke_count: self.details.stroke_count,
+            grade: self.details.grade,
+            radical: self.details.radical,
+        }
+    }
+}
+
+/// On'yomi are kept in katakana and kun'yomi in hiragana, as written in KANJIDIC.
+/// Kun'yomi may mark okurigana with `.` (e.g. `つ.ぐ`) and affixes with `-`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct KanjiReadings {
+    pub on: Vec<String>,
+    pub kun: Vec<String>,
+}
+
+pub type KanjidicData = HashMap<String, KanjiReadings>;
+
+/// What KANJIDIC2 knows about a kanji besides its readings.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct KanjiDetails {
+    /// English meanings
+    pub meanings: Vec<String>,
+    pub stroke_count: Option<u8>,
+    /// 1 to 6 for the Kyōiku grades, 8 for the rest of the Jōyō list and 9 or 10 for Jinmeiyō kanji
+    pub grade: Option<u8>,
+    /// Classical (Kangxi) radical number, 1 to 214
+    pub radical: Option<u8>,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Kanjidic2Entry {
+    pub readings: KanjiReadings,
+    pub details: KanjiDetails,
+}
+
+pub type Kanjidic2Data = HashMap<String, Kanjidic2Entry>;
+
+/// A word the dictionary accepts, with whatever is known about it.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DictEntry {
+    pub word: String,
+    pub readings: Vec<String>,
+    pub parts_of_speech: Vec<String>,
+    /// 1 is the most common word; `None` when the word isn't ranked
+    pub frequency_rank: Option<u32>,
+    /// 1 for N1 through 5 for N5, taken from the easiest JLPT list the word is on
+    pub jlpt_level: Option<u8>,
+    /// Usage notes such as `arch` or `rare`, named as JMdict names them
+    pub tags: Vec<String>,
+    /// Caught by a `WordFilter` rule, so the word only counts in full-dictionary lobbies
+    pub filtered: bool,
+}
+
+impl DictEntry {
+    pub fn info(&self) -> shared::WordInfo {
+        shared::WordInfo {
+            word: self.word.clone(),
+            readings: self.readings.clone(),
+            frequency_rank: self.frequency_rank,
+            jlpt_level: self.jlpt_level,
+        }
+    }
+
+    /// Sort key putting the most useful entries first: ranked words by rank, then JLPT words from N5 up, then the rest.
+    pub fn commonness(&self) -> (u32, u8) {
+        (self.frequency_rank.unwrap_or(u32::MAX), self.jlpt_level.map_or(u8::MAX, |l| 6 - l))
+    }
+}
+
+/// Every accepted word, indexed by spelling and by the kanji it contains.
+#[derive(Clone, Debug, Default)]
+pub struct WordIndex {
+    entries: Vec<DictEntry>,
+    index: HashMap<String, usize>,
+    /// Entries containing each kanji, kept sorted by `DictEntry::commonness`
+    by_kanji: HashMap<char, Vec<usize>>,
+}
+
+impl WordIndex {
+    pub fn get(&self, word: &str) -> Option<&DictEntry> {
+        self.index.get(word).map(|&idx| &self.entries[idx])
+    }
+
+    /// Like `get`, but leaves out filtered words unless `full_dictionary` is set.
+    pub fn lookup(&self, word: &str, full_dictionary: bool) -> Option<&DictEntry> {
+        self.get(word).filter(|e| full_dictionary || !e.filtered)
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.index.contains_key(word)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The `i`th entry in load order, for picking one at random without walking the rest.
+    pub fn entry_at(&self, i: usize) -> Option<&DictEntry> {
+        self.entries.get(i)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DictEntry> {
+        self.entries.iter()
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter().map(|e| &e.word)
+    }
+
+    /// Words containing `kanji`, most common first.
+    pub fn words_with_kanji(&self, kanji: char) -> impl Iterator<Item = &DictEntry> {
+        self.by_kanji.get(&kanji).into_iter().flatten().map(|&idx| &self.entries[idx])
+    }
+
+    /// Words containing every one of `kanji`, most common first.
+    pub fn words_with_all_kanji<'a>(&'a self, kanji: &'a [char]) -> impl Iterator<Item = &'a DictEntry> + 'a {
+        // Walk the shortest list and check the rest against each word
+        let rarest = kanji.iter().copied().min_by_key(|c| self.by_kanji.get(c).map_or(0, Vec::len));
+        rarest.into_iter()
+            .flat_map(|c| self.words_with_kanji(c))
+            .filter(move |e| kanji.iter().all(|&c| e.word.contains(c)))
+    }
+
+    /// Feedback for `word`, from its entry if it has one or else just its readings.
+    pub fn describe(&self, word: &str, readings: &ReadingData) -> shared::WordInfo {
+        self.get(word).map(DictEntry::info).unwrap_or_else(|| shared::WordInfo {
+            word: word.to_string(),
+            readings: readings.get(word).cloned().unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+
+    /// Adds `entry`, merging it into any entry already there for the same word.
+    pub fn insert(&mut self, entry: DictEntry) {
+        match self.index.get(&entry.word) {
+            Some(&idx) => {
+                let existing = &mut self.entries[idx];
+                let previous = existing.commonness();
+                for r in entry.readings {
+                    if !existing.readings.contains(&r) {
+                        existing.readings.push(r);
+                    }
+                }
+                for pos in entry.parts_of_speech {
+                    if !existing.parts_of_speech.contains(&pos) {
+                        existing.parts_of_speech.push(pos);
+                    }
+                }
+                existing.frequency_rank = match (existing.frequency_rank, entry.frequency_rank) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                existing.jlpt_level = existing.jlpt_level.max(entry.jlpt_level);
+                for tag in entry.tags {
+                    if !existing.tags.contains(&tag) {
+                        existing.tags.push(tag);
+                    }
+                }
+                existing.filtered |= entry.filtered;
+                if existing.commonness() != previous {
+                    self.index_kanji(idx, true);
+                }
+            },
+            None => {
+                let idx = self.entries.len();
+                self.index.insert(entry.word.clone(), idx);
+                self.entries.push(entry);
+                self.index_kanji(idx, false);
+            },
+        }
+    }
+
+    /// Puts entry `idx` in its sorted place under each of its kanji, first taking it out if it's `already_indexed`.
+    fn index_kanji(&mut self, idx: usize, already_indexed: bool) {
+        let entries = &self.entries;
+        let key = entries[idx].commonness();
+        let mut kanji: Vec<char> = entries[idx].word.chars().filter(|&c| is_kanji(c)).collect();
+        kanji.sort_unstable();
+        kanji.dedup();
+        for c in kanji {
+            let list = self.by_kanji.entry(c).or_default();
+            if already_indexed {
+                list.retain(|&i| i != idx);
+            }
+            let pos = list.partition_point(|&i| entries[i].commonness() <= key);
+            list.insert(pos, idx);
+        }
+    }
+
+    /// Fills in readings and JLPT levels from the JLPT word lists for words already in the dictionary.
+    pub fn attach_word_levels(&mut self, word_levels: &JlptWordData) {
+        for (idx, level) in word_levels.iter().enumerate() {
+            for (word, readings) in level {
+                if self.contains(word) {
+                    self.insert(DictEntry {
+                        word: word.clone(),
+                        readings: readings.clone(),
+                        jlpt_level: Some(idx as u8 + 1),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    }
 }
 
-pub fn load_dictionary(path: &str) -> Result<DictData, DataLoadError>{
+impl<S: Into<String>> FromIterator<S> for WordIndex {
+    fn from_iter<I: IntoIterator<Item = S>>(words: I) -> Self {
+        let mut dictionary = WordIndex::default();
+        for word in words {
+            dictionary.insert(DictEntry { word: word.into(), ..Default::default() });
+        }
+        dictionary
+    }
+}
+
+/// Tags that mark a word as archaic, obscure or a rare spelling, as JMdict names them.
+pub const DEFAULT_FILTER_TAGS: [&str; 7] = ["arch", "obs", "obsc", "rare", "oK", "rK", "sK"];
+
+/// Rules for dictionary headwords that shouldn't count in a learner game.
+/// Caught words stay in the dictionary but are marked `filtered`.
+#[derive(Clone, Debug, Default)]
+pub struct WordFilter {
+    /// Catch headwords with anything besides kanji and kana, such as ＡＢＣ順 or Ｎ響
+    pub japanese_only: bool,
+    /// Catch words carrying any of these tags
+    pub tags: Vec<String>,
+    /// Catch these words whatever else is known about them
+    pub denylist: HashSet<String>,
+}
+
+impl WordFilter {
+    pub fn catches(&self, entry: &DictEntry) -> bool {
+        (self.japanese_only && !entry.word.chars().all(is_japanese_char))
+            || entry.tags.iter().any(|t| self.tags.contains(t))
+            || self.denylist.contains(&entry.word)
+    }
+
+    /// Marks every word in `dictionary` a rule catches, returning how many were caught.
+    pub fn apply(&self, dictionary: &mut WordIndex) -> usize {
+        let mut caught = 0;
+        for entry in &mut dictionary.entries {
+            entry.filtered = self.catches(entry);
+            caught += entry.filtered as usize;
+        }
+        caught
+    }
+}
+
+/// Kanji, kana and the marks written with them (々, 〆 and the kana repetition marks).
+fn is_japanese_char(c: char) -> bool {
+    is_kanji(c) || is_kana(c) || matches!(c, '〆' | 'ゝ' | 'ゞ' | 'ヽ' | 'ヾ')
+}
+
+/// Loads a denylist of words, one per line. Blank lines and lines starting with `#` are skipped.
+pub fn load_denylist(path: &str) -> Result<HashSet<String>, DataLoadError> {
+    Ok(read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Loads the dictionary CSV: `word[,readings,parts_of_speech,frequency_rank,tags]` and marks the words `filter` catches.
+/// Only the word is required; readings, parts of speech and tags are `;`-separated lists.
+pub fn load_dictionary(path: &str, filter: &WordFilter) -> Result<DictData, DataLoadError>{
     let file: File = File::open(path)?;
-    let mut rdr: Reader<File> = Reader::from_reader(file);
-    let mut word_set = HashSet::new();
+    let mut rdr: Reader<File> = ReaderBuilder::new().flexible(true).from_reader(file);
+    let mut dictionary = WordIndex::default();
 
+    let list = |field: Option<&str>| -> Vec<String> {
+        field.unwrap_or_default().split(';').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect()
+    };
     for result in rdr.records() {
         let record: StringRecord = result?;
-        if let Some(word) = record.get(0) {
-            word_set.insert(word.to_string());
-        }
+        let Some(word) = record.get(0).filter(|w| !w.is_empty()) else { continue };
+        dictionary.insert(DictEntry {
+            word: word.to_string(),
+            readings: list(record.get(1)),
+            parts_of_speech: list(record.get(2)),
+            frequency_rank: record.get(3).and_then(|r| r.trim().parse().ok()),
+            jlpt_level: None,
+            tags: list(record.get(4)),
+            filtered: false,
+        });
     }
+    filter.apply(&mut dictionary);
 
-    Ok(word_set)
+    Ok(dictionary)
 }
 
 /// Loads kanji from multiple CSV files, keeping each file's kanji separate.
@@ -57,6 +368,7 @@ where
                 kanji_vec.push(Kanji {
                         kanji: kanji_char.to_owned(),
                         frequency,
+                        ..Default::default()
                 });
             }
         }
@@ -69,6 +381,53 @@ where
     Ok(kanji_list)
 }
 
+/// Loads the Jōyō list, one kanji per line with no header.
+/// Each kanji keeps the frequency it has on the JLPT `levels`. The few that aren't on them
+/// are ranked together just past the rarest ranked kanji.
+pub fn load_joyo_kanji(path: &str, levels: &KanjiData) -> Result<Vec<Kanji>, DataLoadError> {
+    let frequencies: HashMap<&str, i32> = levels.iter().flatten().map(|k| (k.kanji.as_str(), k.frequency)).collect();
+    let unranked = frequencies.values().copied().max().unwrap_or(0).max(0) + 1;
+    let mut rdr = ReaderBuilder::new().has_headers(false).flexible(true).from_path(path)?;
+    let mut joyo = Vec::new();
+
+    for result in rdr.records() {
+        let record: StringRecord = result?;
+        let Some(kanji) = record.get(0).map(str::trim).filter(|k| !k.is_empty()) else { continue };
+        joyo.push(Kanji {
+            kanji: kanji.to_string(),
+            frequency: frequencies.get(kanji).copied().filter(|&f| f > 0).unwrap_or(unranked),
+            ..Default::default()
+        });
+    }
+
+    if joyo.is_empty() {
+        return Err(DataLoadError::EmptyFile(path.into()));
+    }
+    Ok(joyo)
+}
+
+/// Loads the Kyōiku grade list, `kanji,grade` lines with no header, into one set per grade from 1 to 6.
+/// Kanji are copied from `joyo` so they keep its frequencies, readings and details.
+pub fn load_kyoiku_kanji(path: &str, joyo: &[Kanji]) -> Result<KanjiData, DataLoadError> {
+    let by_kanji: HashMap<&str, &Kanji> = joyo.iter().map(|k| (k.kanji.as_str(), k)).collect();
+    let mut rdr = ReaderBuilder::new().has_headers(false).flexible(true).from_path(path)?;
+    let mut grades: KanjiData = vec![Vec::new(); 6];
+
+    for result in rdr.records() {
+        let record: StringRecord = result?;
+        let Some(kanji) = record.get(0).map(str::trim).filter(|k| !k.is_empty()) else { continue };
+        let Some(grade) = record.get(1).and_then(|g| g.trim().parse::<usize>().ok()).filter(|g| (1..=6).contains(g)) else { continue };
+        let entry = by_kanji.get(kanji).map(|&k| k.clone())
+            .unwrap_or_else(|| Kanji { kanji: kanji.to_string(), ..Default::default() });
+        grades[grade - 1].push(entry);
+    }
+
+    if grades.iter().all(Vec::is_empty) {
+        return Err(DataLoadError::EmptyFile(path.into()));
+    }
+    Ok(grades)
+}
+
 pub fn load_jlpt_words<I, P>(paths: I) -> Result<JlptWordData, DataLoadError>
 where
     I: IntoIterator<Item = P>,
@@ -93,3 +452,239 @@ where
 
     Ok(word_levels)
 }
+
+/// Loads word ranks from a CSV with a header and `word,rank` rows, 1 being the word to favour most.
+/// A word listed more than once keeps its best rank.
+pub fn load_word_ranks(path: &str) -> Result<WordRankData, DataLoadError> {
+    let mut rdr: Reader<File> = Reader::from_path(path)?;
+    let mut ranks: WordRankData = HashMap::new();
+
+    for result in rdr.records() {
+        let record: StringRecord = result?;
+        let (Some(word), Some(rank)) = (record.get(0), record.get(1).and_then(|r| r.trim().parse::<u32>().ok())) else { continue };
+        if word.is_empty() { continue; }
+        ranks.entry(word.to_string()).and_modify(|r| *r = (*r).min(rank)).or_insert(rank);
+    }
+
+    if ranks.is_empty() {
+        return Err(DataLoadError::EmptyFile(path.into()));
+    }
+
+    Ok(ranks)
+}
+
+/// Ranks for the JLPT words: from `ranks` where listed, otherwise from the word's dictionary entry.
+pub fn rank_jlpt_words(word_levels: &JlptWordData, ranks: &WordRankData, dictionary: &DictData) -> WordRankData {
+    word_levels.iter()
+        .flat_map(|level| level.keys())
+        .filter_map(|word| {
+            let rank = ranks.get(word).copied().or_else(|| dictionary.get(word)?.frequency_rank)?;
+            Some((word.clone(), rank))
+        })
+        .collect()
+}
+
+/// Merges every JLPT level into a single word -> readings lookup.
+/// Used by modes that need the reading of an arbitrary submitted word.
+pub fn index_readings(word_levels: &JlptWordData) -> ReadingData {
+    let mut readings: ReadingData = HashMap::new();
+    for level in word_levels {
+        for (word, word_readings) in level {
+            let entry = readings.entry(word.clone()).or_default();
+            for r in word_readings {
+                if !entry.contains(r) {
+                    entry.push(r.clone());
+                }
+            }
+        }
+    }
+
+    readings
+}
+
+/// Inverts each JLPT level's word map into reading -> kanji spellings.
+/// Kana-only words are left out since they don't exercise writing kanji.
+pub fn index_spellings(word_levels: &JlptWordData) -> SpellingData {
+    word_levels.iter().map(|level| {
+        let mut spellings: HashMap<String, Vec<String>> = HashMap::new();
+        for (word, word_readings) in level {
+            if word.chars().all(is_kana) {
+                continue;
+            }
+            for r in word_readings {
+                let entry = spellings.entry(r.clone()).or_default();
+                if !entry.contains(word) {
+                    entry.push(word.clone());
+                }
+            }
+        }
+        spellings
+    }).collect()
+}
+
+/// Loads English glosses from a JMdict-derived CSV with a `word,gloss` header.
+/// A word may span several rows and a gloss cell may hold several senses separated by `;`.
+pub fn load_glosses(path: &str) -> Result<GlossData, DataLoadError> {
+    let mut rdr: Reader<File> = Reader::from_path(path)?;
+    let mut glosses: GlossData = HashMap::new();
+
+    for result in rdr.records() {
+        let record: StringRecord = result?;
+        let (Some(word), Some(gloss)) = (record.get(0), record.get(1)) else { continue };
+        if word.is_empty() { continue; }
+
+        let entry = glosses.entry(word.to_string()).or_default();
+        for sense in gloss.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            if !entry.iter().any(|g| g == sense) {
+                entry.push(sense.to_string());
+            }
+        }
+    }
+
+    if glosses.is_empty() {
+        return Err(DataLoadError::EmptyFile(path.into()));
+    }
+
+    Ok(glosses)
+}
+
+/// Loads readings from a UTF-8 KANJIDIC-style file: one kanji per line followed by
+/// space-separated fields. Katakana fields are on'yomi, hiragana fields are kun'yomi,
+/// `{...}` meanings and ASCII index codes are skipped, and name readings after `T1`/`T2` are ignored.
+pub fn load_kanjidic(path: &str) -> Result<KanjidicData, DataLoadError> {
+    let content = read_to_string(path)?;
+    let mut kanjidic: KanjidicData = HashMap::new();
+
+    for line in content.lines() {
+        if line.starts_with('#') { continue; }
+        let mut fields = line.split_whitespace();
+        let Some(kanji) = fields.next() else { continue };
+
+        let mut readings = KanjiReadings::default();
+        for field in fields {
+            if field == "T1" || field == "T2" || field.starts_with('{') { break; }
+            let Some(first) = field.trim_start_matches('-').chars().next() else { continue };
+            match first {
+                'ァ'..='ヺ' => readings.on.push(field.to_string()),
+                'ぁ'..='ゖ' => readings.kun.push(field.to_string()),
+                _ => {}
+            }
+        }
+
+        if !readings.on.is_empty() || !readings.kun.is_empty() {
+            kanjidic.insert(kanji.to_string(), readings);
+        }
+    }
+
+    if kanjidic.is_empty() {
+        return Err(DataLoadError::EmptyFile(path.into()));
+    }
+
+    Ok(kanjidic)
+}
+
+/// Moves kanji found in fewer than `min_words` unfiltered dictionary words out of `kanji_levels`,
+/// returning them level by level so they can be kept as a separate pool.
+pub fn split_unplayable_kanji(kanji_levels: &mut KanjiData, dictionary: &DictData, min_words: usize) -> KanjiData {
+    kanji_levels.iter_mut()
+        .map(|level| {
+            let (playable, hard) = std::mem::take(level).into_iter().partition(|k| {
+                k.kanji.chars().next().is_some_and(|c| dictionary.words_with_kanji(c).filter(|e| !e.filtered).take(min_words).count() >= min_words)
+            });
+            *level = playable;
+            hard
+        })
+        .collect()
+}
+
+/// Copies KANJIDIC readings onto the matching kanji of every level.
+pub fn attach_kanji_readings(kanji_levels: &mut KanjiData, kanjidic: &KanjidicData) {
+    for kanji in kanji_levels.iter_mut().flatten() {
+        if let Some(readings) = kanjidic.get(&kanji.kanji) {
+            kanji.readings = readings.clone();
+        }
+    }
+}
+
+/// Loads a KANJIDIC2 XML file: readings, English meanings, stroke count, school grade and classical radical per kanji.
+/// Only the first stroke count is kept; later ones are common miscounts.
+pub fn load_kanjidic2(path: &str) -> Result<Kanjidic2Data, DataLoadError> {
+    let mut reader = XmlReader::from_file(path)?;
+    let mut kanjidic: Kanjidic2Data = HashMap::new();
+
+    let mut buf = Vec::new();
+    let mut literal = String::new();
+    let mut entry = Kanjidic2Entry::default();
+    let mut text = String::new();
+    // The `r_type`, `m_lang` or `rad_type` attribute of the element being read
+    let mut kind = None::<String>;
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => {
+                text.clear();
+                kind = None;
+                let attribute = match e.local_name().as_ref() {
+                    b"character" => {
+                        literal.clear();
+                        entry = Kanjidic2Entry::default();
+                        None
+                    },
+                    b"reading" => Some("r_type"),
+                    b"meaning" => Some("m_lang"),
+                    b"rad_value" => Some("rad_type"),
+                    _ => None,
+                };
+                if let Some(attribute) = attribute {
+                    kind = e.try_get_attribute(attribute).map_err(quick_xml::Error::from)?
+                        .map(|a| a.unescape_value().map(|v| v.into_owned()))
+                        .transpose()?;
+                }
+            },
+            Event::Text(t) => text.push_str(&t.decode().map_err(quick_xml::Error::from)?),
+            Event::GeneralRef(r) => match r.resolve_char_ref()? {
+                Some(c) => text.push(c),
+                None => {
+                    let name = r.decode().map_err(quick_xml::Error::from)?;
+                    text.push_str(resolve_predefined_entity(&name).unwrap_or(&name));
+                },
+            },
+            Event::End(e) => {
+                let value = std::mem::take(&mut text);
+                let details = &mut entry.details;
+                match (e.local_name().as_ref(), kind.as_deref()) {
+                    (b"literal", _) => literal = value,
+                    (b"reading", Some("ja_on")) => entry.readings.on.push(value),
+                    (b"reading", Some("ja_kun")) => entry.readings.kun.push(value),
+                    // Meanings without a language are English
+                    (b"meaning", None) => details.meanings.push(value),
+                    (b"stroke_count", _) if details.stroke_count.is_none() => details.stroke_count = value.parse().ok(),
+                    (b"grade", _) => details.grade = value.parse().ok(),
+                    (b"rad_value", Some("classical")) => details.radical = value.parse().ok(),
+                    (b"character", _) if !literal.is_empty() => {
+                        kanjidic.insert(std::mem::take(&mut literal), std::mem::take(&mut entry));
+                    },
+                    _ => {},
+                }
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+        buf.clear();
+    }
+
+    if kanjidic.is_empty() {
+        return Err(DataLoadError::EmptyFile(path.into()));
+    }
+
+    Ok(kanjidic)
+}
+
+/// Copies KANJIDIC2 readings and details onto the matching kanji of every level.
+pub fn attach_kanji_details(kanji_levels: &mut KanjiData, kanjidic: &Kanjidic2Data) {
+    for kanji in kanji_levels.iter_mut().flatten() {
+        if let Some(entry) = kanjidic.get(&kanji.kanji) {
+            kanji.readings = entry.readings.clone();
+            kanji.details = entry.details.clone();
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::sync::LazyLock;
+use crate::utils::is_kana;
+
+/// Word classes a rule can start from or lead to, as bit flags.
+/// `FINAL` only describes the submitted word, so rules for endings such as た can't be stacked.
+type WordType = u8;
+const FINAL: WordType = 1;
+const V1: WordType = 1 << 1;
+const V5: WordType = 1 << 2;
+const ADJ_I: WordType = 1 << 3;
+const VS: WordType = 1 << 4;
+/// A polite ～ます form
+const MASU: WordType = 1 << 5;
+/// A ～て form, which ～ている hangs off
+const TE: WordType = 1 << 6;
+const ANY: WordType = u8::MAX;
+
+/// Undoes one inflection: a word of type `from_type` ending in `from` becomes one of type `to_type` ending in `to`.
+struct Rule {
+    from: String,
+    to: &'static str,
+    from_type: WordType,
+    to_type: WordType,
+}
+
+impl Rule {
+    fn new(from: impl Into<String>, to: &'static str, from_type: WordType, to_type: WordType) -> Self {
+        Self { from: from.into(), to, from_type, to_type }
+    }
+}
+
+/// Godan verb endings with their a-, i-, e- and o-row stems and their ～て and ～た endings.
+const GODAN: [(&str, &str, &str, &str, &str, &str, &str); 9] = [
+    ("う", "わ", "い", "え", "お", "って", "った"),
+    ("く", "か", "き", "け", "こ", "いて", "いた"),
+    ("ぐ", "が", "ぎ", "げ", "ご", "いで", "いだ"),
+    ("す", "さ", "し", "せ", "そ", "して", "した"),
+    ("つ", "た", "ち", "て", "と", "って", "った"),
+    ("ぬ", "な", "に", "ね", "の", "んで", "んだ"),
+    ("ぶ", "ば", "び", "べ", "ぼ", "んで", "んだ"),
+    ("む", "ま", "み", "め", "も", "んで", "んだ"),
+    ("る", "ら", "り", "れ", "ろ", "って", "った"),
+];
+
+/// Ichidan verb endings that replace the final る.
+const ICHIDAN: &[(&str, WordType, WordType)] = &[
+    ("ない", ADJ_I, V1),
+    ("ます", MASU, V1),
+    ("たい", ADJ_I, V1),
+    ("た", FINAL, V1),
+    ("て", FINAL | TE, V1),
+    ("られる", V1, V1),
+    ("させる", V1, V1),
+    ("れば", FINAL, V1),
+    ("よう", FINAL, V1),
+    ("ろ", FINAL, V1),
+    ("なさい", FINAL, V1),
+];
+
+/// Endings that don't depend on the verb class: politeness, progressive and conditional forms.
+const COMMON: &[(&str, &str, WordType, WordType)] = &[
+    ("ました", "ます", FINAL, MASU),
+    ("ません", "ます", FINAL, MASU),
+    ("ませんでした", "ます", FINAL, MASU),
+    ("ましょう", "ます", FINAL, MASU),
+    ("まして", "ます", FINAL, MASU),
+    ("ている", "て", V1, TE),
+    ("でいる", "で", V1, TE),
+    ("てる", "て", V1, TE),
+    ("でる", "で", V1, TE),
+    ("たら", "た", FINAL, FINAL),
+    ("だら", "だ", FINAL, FINAL),
+    ("たり", "た", FINAL, FINAL),
+    ("だり", "だ", FINAL, FINAL),
+];
+
+const ADJECTIVE: &[(&str, WordType)] = &[
+    ("くない", ADJ_I),
+    ("かった", FINAL),
+    ("くて", FINAL | TE),
+    ("ければ", FINAL),
+    ("く", FINAL),
+];
+
+/// する and 行く don't follow the tables above.
+const IRREGULAR: &[(&str, &str, WordType, WordType)] = &[
+    ("した", "する", FINAL, VS),
+    ("して", "する", FINAL | TE, VS),
+    ("しない", "する", ADJ_I, VS),
+    ("します", "する", MASU, VS),
+    ("したい", "する", ADJ_I, VS),
+    ("される", "する", V1, VS),
+    ("させる", "する", V1, VS),
+    ("すれば", "する", FINAL, VS),
+    ("しよう", "する", FINAL, VS),
+    ("しろ", "する", FINAL, VS),
+    ("行った", "行く", FINAL, V5),
+    ("行って", "行く", FINAL | TE, V5),
+    ("来い", "来る", FINAL, V1),
+];
+
+static RULES: LazyLock<Vec<Rule>> = LazyLock::new(|| {
+    let mut rules = Vec::new();
+    for &(dict, a, i, e, o, te, ta) in &GODAN {
+        rules.push(Rule::new(format!("{a}ない"), dict, ADJ_I, V5));
+        rules.push(Rule::new(format!("{a}れる"), dict, V1, V5));
+        rules.push(Rule::new(format!("{a}せる"), dict, V1, V5));
+        rules.push(Rule::new(format!("{i}ます"), dict, MASU, V5));
+        rules.push(Rule::new(format!("{i}たい"), dict, ADJ_I, V5));
+        rules.push(Rule::new(format!("{i}なさい"), dict, FINAL, V5));
+        rules.push(Rule::new(format!("{e}る"), dict, V1, V5));
+        rules.push(Rule::new(format!("{e}ば"), dict, FINAL, V5));
+        rules.push(Rule::new(e, dict, FINAL, V5));
+        rules.push(Rule::new(format!("{o}う"), dict, FINAL, V5));
+        rules.push(Rule::new(te, dict, FINAL | TE, V5));
+        rules.push(Rule::new(ta, dict, FINAL, V5));
+    }
+    rules.extend(ICHIDAN.iter().map(|&(from, from_type, to_type)| Rule::new(from, "る", from_type, to_type)));
+    rules.extend(ADJECTIVE.iter().map(|&(from, from_type)| Rule::new(from, "い", from_type, ADJ_I)));
+    rules.extend(COMMON.iter().map(|&(from, to, from_type, to_type)| Rule::new(from, to, from_type, to_type)));
+    rules.extend(IRREGULAR.iter().map(|&(from, to, from_type, to_type)| Rule::new(from, to, from_type, to_type)));
+    rules
+});
+
+/// Every form `word` could be an inflection of, starting with `word` itself.
+/// Most candidates aren't real words; callers keep the ones found in their dictionary.
+pub fn dictionary_forms(word: &str) -> Vec<String> {
+    let mut forms = vec![(word.to_string(), ANY)];
+    let mut idx = 0;
+    while idx < forms.len() {
+        let (form, word_type) = forms[idx].clone();
+        for rule in RULES.iter() {
+            if word_type & rule.from_type == 0 {
+                continue;
+            }
+            // The stem has to survive, so a bare ending is never taken for a word; 行った and the like are whole words
+            let Some(stem) = form.strip_suffix(rule.from.as_str()).filter(|s| !s.is_empty() || !rule.from.chars().all(is_kana)) else {
+                continue;
+            };
+            let candidate = format!("{}{}", stem, rule.to);
+            if !forms.iter().any(|(f, t)| *f == candidate && *t == rule.to_type) {
+                forms.push((candidate, rule.to_type));
+            }
+        }
+        idx += 1;
+    }
+
+    let mut words: Vec<String> = Vec::with_capacity(forms.len());
+    for (form, _) in forms {
+        if !words.contains(&form) {
+            words.push(form);
+        }
+    }
+    words
+}
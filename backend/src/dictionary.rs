@@ -0,0 +1,221 @@
+use crate::{
+    data::{load_dictionary, load_glosses, DictData, DictEntry, GlossData, WordFilter},
+    error::DataLoadError,
+};
+use quick_xml::{escape::resolve_predefined_entity, events::Event, Reader};
+use std::{collections::HashMap, path::PathBuf};
+
+/// Everything a dictionary source provides: the words the game accepts and their English glosses.
+pub struct DictionaryContents {
+    pub words: DictData,
+    pub glosses: GlossData,
+}
+
+/// A source of dictionary data, read once at startup.
+pub trait Dictionary: Send + Sync {
+    /// Where the data comes from, for logs
+    fn describe(&self) -> String;
+    fn load(&self) -> Result<DictionaryContents, DataLoadError>;
+}
+
+/// The bundled CSVs: `kanji_words.csv` for words and an optional `jmdict_glosses.csv` for glosses.
+pub struct CsvDictionary {
+    pub words_path: String,
+    pub glosses_path: String,
+    pub filter: WordFilter,
+}
+
+impl Dictionary for CsvDictionary {
+    fn describe(&self) -> String {
+        format!("CSV files {} and {}", self.words_path, self.glosses_path)
+    }
+
+    fn load(&self) -> Result<DictionaryContents, DataLoadError> {
+        let words = load_dictionary(&self.words_path, &self.filter)?;
+        // Glosses are optional: without them only the Meaning mode is unavailable
+        let glosses = load_glosses(&self.glosses_path).unwrap_or_else(|e| {
+            tracing::warn!("No English glosses loaded from {}: {}", self.glosses_path, e);
+            HashMap::new()
+        });
+        Ok(DictionaryContents { words, glosses })
+    }
+}
+
+/// An upstream JMdict XML release such as `JMdict_e.xml`.
+/// Every kanji spelling becomes a word; glosses are kept for kanji spellings and for kana-only entries.
+pub struct JmdictDictionary {
+    pub path: PathBuf,
+    pub filter: WordFilter,
+}
+
+/// Rank given to words JMdict marks as common without an `nfXX` frequency band.
+const COMMON_RANK: u32 = 24_001;
+
+/// JMdict ranks words in bands of 500 (`nf01` to `nf48`), so a band maps to the first rank in it.
+/// Words on the other common-word lists (`news1`, `ichi1`, `spec1`, `gai1`) rank just after the bands.
+fn priority_rank(tag: &str) -> Option<u32> {
+    if let Some(band) = tag.strip_prefix("nf").and_then(|b| b.parse::<u32>().ok()) {
+        return Some(band.saturating_sub(1) * 500 + 1);
+    }
+    matches!(tag, "news1" | "ichi1" | "spec1" | "gai1").then_some(COMMON_RANK)
+}
+
+#[derive(Default)]
+struct KanjiElement {
+    spelling: String,
+    rank: Option<u32>,
+    /// Notes on this spelling from `ke_inf`, such as `oK` for an outdated kanji form
+    tags: Vec<String>,
+}
+
+#[derive(Default)]
+struct ReadingElement {
+    reading: String,
+    /// Set by `re_nokanji`: the reading isn't a true reading of any kanji spelling
+    no_kanji: bool,
+    /// Kanji spellings this reading is limited to, from `re_restr`
+    restricted_to: Vec<String>,
+}
+
+#[derive(Default)]
+struct JmdictEntry {
+    kanji: Vec<KanjiElement>,
+    readings: Vec<ReadingElement>,
+    parts_of_speech: Vec<String>,
+    /// `misc` tags of each sense
+    sense_tags: Vec<Vec<String>>,
+    glosses: Vec<String>,
+}
+
+impl JmdictEntry {
+    fn add_to(self, contents: &mut DictionaryContents) {
+        let mut add_glosses = |word: &str| {
+            if self.glosses.is_empty() {
+                return;
+            }
+            let entry = contents.glosses.entry(word.to_string()).or_default();
+            for gloss in &self.glosses {
+                if !entry.contains(gloss) {
+                    entry.push(gloss.clone());
+                }
+            }
+        };
+        if self.kanji.is_empty() {
+            for reading in &self.readings {
+                add_glosses(&reading.reading);
+            }
+        }
+        for kanji in &self.kanji {
+            add_glosses(&kanji.spelling);
+        }
+
+        // A note like `arch` only describes the word when every sense has it
+        let mut senses = self.sense_tags.iter();
+        let entry_tags: Vec<String> = senses.next().map(|first| {
+            first.iter().filter(|t| senses.clone().all(|s| s.contains(t))).cloned().collect()
+        }).unwrap_or_default();
+
+        for kanji in self.kanji {
+            let mut tags = kanji.tags;
+            for tag in &entry_tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+            let readings = self.readings.iter()
+                .filter(|r| !r.no_kanji && (r.restricted_to.is_empty() || r.restricted_to.contains(&kanji.spelling)))
+                .map(|r| r.reading.clone())
+                .collect();
+            contents.words.insert(DictEntry {
+                word: kanji.spelling,
+                readings,
+                parts_of_speech: self.parts_of_speech.clone(),
+                frequency_rank: kanji.rank,
+                jlpt_level: None,
+                tags,
+                filtered: false,
+            });
+        }
+    }
+}
+
+impl Dictionary for JmdictDictionary {
+    fn describe(&self) -> String {
+        format!("JMdict file {}", self.path.display())
+    }
+
+    fn load(&self) -> Result<DictionaryContents, DataLoadError> {
+        let mut reader = Reader::from_file(&self.path)?;
+        let mut contents = DictionaryContents { words: DictData::default(), glosses: GlossData::new() };
+
+        let mut buf = Vec::new();
+        let mut entry = JmdictEntry::default();
+        // Text of the innermost element, which in JMdict is always a leaf when it has text
+        let mut text = String::new();
+        let mut english_gloss = false;
+        loop {
+            match reader.read_event_into(&mut buf)? {
+                Event::Start(e) => {
+                    text.clear();
+                    match e.local_name().as_ref() {
+                        b"entry" => entry = JmdictEntry::default(),
+                        b"k_ele" => entry.kanji.push(KanjiElement::default()),
+                        b"r_ele" => entry.readings.push(ReadingElement::default()),
+                        b"sense" => entry.sense_tags.push(Vec::new()),
+                        b"gloss" => {
+                            let lang = e.try_get_attribute("xml:lang").map_err(quick_xml::Error::from)?
+                                .map(|a| a.unescape_value().map(|v| v.into_owned()))
+                                .transpose()?;
+                            english_gloss = lang.is_none_or(|l| l == "eng");
+                        },
+                        _ => {},
+                    }
+                },
+                Event::Empty(e) if e.local_name().as_ref() == b"re_nokanji" => {
+                    if let Some(r) = entry.readings.last_mut() {
+                        r.no_kanji = true;
+                    }
+                },
+                Event::Text(t) => text.push_str(&t.decode().map_err(quick_xml::Error::from)?),
+                // JMdict writes parts of speech as entities such as `&v5k;`, which stand for their own names here
+                Event::GeneralRef(r) => match r.resolve_char_ref()? {
+                    Some(c) => text.push(c),
+                    None => {
+                        let name = r.decode().map_err(quick_xml::Error::from)?;
+                        text.push_str(resolve_predefined_entity(&name).unwrap_or(&name));
+                    },
+                },
+                Event::End(e) => {
+                    let value = std::mem::take(&mut text);
+                    match e.local_name().as_ref() {
+                        b"keb" => if let Some(k) = entry.kanji.last_mut() { k.spelling = value },
+                        b"ke_pri" => if let Some(k) = entry.kanji.last_mut() {
+                            k.rank = match (k.rank, priority_rank(&value)) {
+                                (Some(a), Some(b)) => Some(a.min(b)),
+                                (a, b) => a.or(b),
+                            };
+                        },
+                        b"reb" => if let Some(r) = entry.readings.last_mut() { r.reading = value },
+                        b"re_restr" => if let Some(r) = entry.readings.last_mut() { r.restricted_to.push(value) },
+                        b"re_nokanji" => if let Some(r) = entry.readings.last_mut() { r.no_kanji = true },
+                        b"ke_inf" => if let Some(k) = entry.kanji.last_mut() { k.tags.push(value) },
+                        b"misc" => if let Some(tags) = entry.sense_tags.last_mut() { tags.push(value) },
+                        b"pos" if !entry.parts_of_speech.contains(&value) => entry.parts_of_speech.push(value),
+                        b"gloss" if english_gloss && !value.is_empty() => entry.glosses.push(value),
+                        b"entry" => std::mem::take(&mut entry).add_to(&mut contents),
+                        _ => {},
+                    }
+                },
+                Event::Eof => break,
+                _ => {},
+            }
+            buf.clear();
+        }
+
+        if contents.words.is_empty() {
+            return Err(DataLoadError::EmptyFile(self.path.clone()));
+        }
+        self.filter.apply(&mut contents.words);
+        Ok(contents)
+    }
+}
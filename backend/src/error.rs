@@ -12,6 +12,8 @@ pub enum DataLoadError {
     Io(#[from] std::io::Error),
     #[error("CSV parse error: {0}")]
     Csv(#[from] csv::Error),
+    #[error("XML parse error: {0}")]
+    Xml(#[from] quick_xml::Error),
     #[error("Empty data file")]
     EmptyFile(std::path::PathBuf),
 }
@@ -1,40 +1,57 @@
 pub mod api;
+pub mod bot;
 pub mod data;
 pub mod db;
+pub mod deinflect;
+pub mod dictionary;
 pub mod error;
 pub mod models;
 pub mod types;
 pub mod lobby;
+pub mod review;
+pub mod romaji;
 pub mod state;
+pub mod tournament;
 pub mod utils;
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::{HashMap, HashSet};
-    use data::Kanji;
+    use data::{Kanji, KanjiDetails, KanjiReadings, GameData, index_readings, index_spellings, load_dictionary, load_glosses, load_kanjidic, load_kanjidic2};
     use std::sync::Arc;
     use error::AppError;
     use crate::{lobby::LobbyState, state::AppState};
-    use shared::{ActivePrompt, LobbyId, PlayerId, GameStatus, GameSettings, ApiContext};
+    use shared::{ActivePrompt, DifficultySet, LobbyId, PlayerId, GameStatus, GameSettings, ApiContext};
     use utils::generate_lobby_id;
 
     fn create_test_lobby() -> LobbyState {
         let test_kanji_list = Arc::new(vec![
             vec![
-                Kanji { kanji: "日".to_string(), frequency: 0 },
-                Kanji { kanji: "月".to_string(), frequency: 0 },
-                Kanji { kanji: "屈".to_string(), frequency: 0 },
-                Kanji { kanji: "理".to_string(), frequency: 0 },
-                Kanji { kanji: "総".to_string(), frequency: 0 },
-                Kanji { kanji: "辱".to_string(), frequency: 0 },
-                Kanji { kanji: "酷".to_string(), frequency: 0 },
-                Kanji { kanji: "関".to_string(), frequency: 0 },
-                Kanji { kanji: "糸".to_string(), frequency: 0 },
-                Kanji { kanji: "木".to_string(), frequency: 0 },
+                Kanji { kanji: "日".to_string(), frequency: 0, readings: KanjiReadings {
+                    on: vec!["ニチ".to_string(), "ジツ".to_string()],
+                    kun: vec!["ひ".to_string(), "-び".to_string(), "-か".to_string()],
+                }, details: KanjiDetails {
+                    meanings: vec!["day".to_string(), "sun".to_string(), "Japan".to_string()],
+                    stroke_count: Some(4),
+                    grade: Some(1),
+                    radical: Some(72),
+                } },
+                Kanji { kanji: "月".to_string(), frequency: 0, readings: KanjiReadings {
+                    on: vec!["ゲツ".to_string(), "ガツ".to_string()],
+                    kun: vec!["つき".to_string()],
+                }, ..Default::default() },
+                Kanji { kanji: "屈".to_string(), frequency: 0, ..Default::default() },
+                Kanji { kanji: "理".to_string(), frequency: 0, ..Default::default() },
+                Kanji { kanji: "総".to_string(), frequency: 0, ..Default::default() },
+                Kanji { kanji: "辱".to_string(), frequency: 0, ..Default::default() },
+                Kanji { kanji: "酷".to_string(), frequency: 0, ..Default::default() },
+                Kanji { kanji: "関".to_string(), frequency: 0, ..Default::default() },
+                Kanji { kanji: "糸".to_string(), frequency: 0, ..Default::default() },
+                Kanji { kanji: "木".to_string(), frequency: 0, ..Default::default() },
             ],
         ]);
-        let test_dict_list = Arc::new(HashSet::from([
+        let test_dict_list = Arc::new(data::DictData::from_iter([
             "日本".to_string(),
             "弄り回す".to_string(),
             "月曜日".to_string(),
@@ -54,11 +71,30 @@ mod tests {
                 map.insert("木曜日".to_string(), vec!["もくようび".to_string()]);
                 map.insert("日記".to_string(), vec!["にっき".to_string()]);
                 map.insert("理由".to_string(), vec!["りゆう".to_string()]);
+                map.insert("切手".to_string(), vec!["きって".to_string()]);
+                map.insert("手紙".to_string(), vec!["てがみ".to_string()]);
+                map.insert("二本".to_string(), vec!["にほん".to_string()]);
+                map.insert("ありがとう".to_string(), vec!["ありがとう".to_string()]);
                 map
             },
         ]);
+        let test_gloss_list = Arc::new(HashMap::from([
+            ("手紙".to_string(), vec!["letter".to_string(), "note".to_string()]),
+        ]));
 
-        LobbyState::new(test_kanji_list, test_words_list, test_dict_list, None, None)
+        let lobby = LobbyState::new(&GameData {
+            readings: Arc::new(index_readings(&test_words_list)),
+            spellings: Arc::new(index_spellings(&test_words_list)),
+            glosses: test_gloss_list,
+            word_ranks: Arc::new(HashMap::from([("日本".to_string(), 1), ("ありがとう".to_string(), 5000)])),
+            kanji: test_kanji_list,
+            hard_kanji: Arc::new(Vec::new()),
+            words: test_words_list,
+            dictionary: test_dict_list,
+        }, None, None);
+        // The fixture only has kanji for N1, and empty sets can't be played
+        lobby.settings.write(|s| s.difficulty_levels = vec![DifficultySet::Jlpt(1)]);
+        lobby
     }
 
     #[test]
@@ -266,7 +302,7 @@ mod tests {
             .unwrap();
 
         let new_settings = GameSettings {
-            difficulty_levels: vec!["N5".to_string(), "N4".to_string()],
+            difficulty_levels: vec![DifficultySet::Jlpt(5), DifficultySet::Jlpt(4)],
             time_limit_seconds: Some(60),
             max_players: 10,
             weighted: false,
@@ -458,6 +494,1011 @@ mod tests {
         assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Finished);
     }
 
+    #[test]
+    fn test_index_spellings_groups_homophones() {
+        let lobby = create_test_lobby();
+        let mut spellings = lobby.spelling_list[0]["にほん"].clone();
+        spellings.sort();
+        assert_eq!(spellings, vec!["二本".to_string(), "日本".to_string()]);
+        // Kana-only words have no kanji spelling to practise
+        assert!(!lobby.spelling_list[0].contains_key("ありがとう"));
+    }
+
+    #[test]
+    fn test_reverse_vocab_accepts_any_kanji_spelling() {
+        let (lobby, leader) = setup_deathmatch_playing();
+        lobby.settings.write(|s| s.content_mode = shared::ContentMode::ReverseVocab);
+        let set_prompt = || lobby.current_prompt.write(|p| *p = Some(ActivePrompt::ReverseVocab {
+            reading: "にほん".to_string(),
+            words: lobby.spelling_list[0]["にほん"].clone(),
+        }));
+
+        set_prompt();
+        lobby.process_guess(&leader, "にほん").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 0);
+
+        lobby.process_guess(&leader, "二本").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+
+        set_prompt();
+        lobby.process_guess(&leader, "日本").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_reverse_vocab_prompt_shows_reading() {
+        let (lobby, _leader) = setup_deathmatch_playing();
+        lobby.settings.write(|s| s.content_mode = shared::ContentMode::ReverseVocab);
+        let text = lobby.generate_random_prompt(false, false).unwrap();
+        assert!(lobby.spelling_list[0].contains_key(&text));
+    }
+
+    #[test]
+    fn test_meaning_prompt_accepts_kanji_or_kana() {
+        let (lobby, leader) = setup_deathmatch_playing();
+        lobby.settings.write(|s| s.content_mode = shared::ContentMode::Meaning);
+
+        // Only 手紙 has a gloss in the fixture
+        assert_eq!(lobby.generate_random_prompt(false, false).unwrap(), "letter; note");
+        lobby.process_guess(&leader, "てがみ").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+
+        lobby.generate_random_prompt(false, false).unwrap();
+        lobby.process_guess(&leader, "手紙").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_vocab_answer_strictness() {
+        use shared::AnswerStrictness::{Lenient, Standard, Strict};
+        let lobby = create_test_lobby();
+        let check = |readings: &[&str], input: &str, strictness| {
+            let prompt = ActivePrompt::Vocab { word: "日本".to_string(), readings: readings.iter().map(|r| r.to_string()).collect() };
+            utils::check_prompt(&prompt, input, &lobby.dict_list, &lobby.reading_list, strictness, true, false).is_some()
+        };
+
+        assert!(check(&["にほん"], "にほん", Strict));
+        assert!(!check(&["にほん"], "ニホン", Strict));
+        assert!(check(&["にほん"], "ニホン", Standard));
+        // Half-width katakana, a full-width space and trailing punctuation
+        assert!(check(&["にほん"], "ﾆﾎﾝ", Standard));
+        assert!(check(&["にほん"], "　にほん。", Standard));
+        assert!(!check(&["にほん"], "　にほん。", Strict));
+
+        // Long-vowel marks only stand in for their vowel when lenient
+        assert!(!check(&["コーヒー"], "こおひい", Standard));
+        assert!(check(&["コーヒー"], "こおひい", Lenient));
+        assert!(check(&["こおひい"], "コーヒー", Lenient));
+        assert!(!check(&["にほん"], "にほ", Lenient));
+    }
+
+    #[test]
+    fn test_strictness_setting_applies_to_guesses() {
+        let (lobby, leader) = setup_deathmatch_playing();
+        let set_prompt = || lobby.current_prompt.write(|p| *p = Some(ActivePrompt::Vocab {
+            word: "日本".to_string(),
+            readings: vec!["にほん".to_string()],
+        }));
+
+        lobby.settings.write(|s| s.answer_strictness = shared::AnswerStrictness::Strict);
+        set_prompt();
+        lobby.process_guess(&leader, "ニホン").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 0);
+
+        lobby.settings.write(|s| s.answer_strictness = shared::AnswerStrictness::Standard);
+        lobby.process_guess(&leader, "ニホン").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_load_glosses_merges_senses() {
+        let path = std::env::temp_dir().join(format!("glosses_{}.csv", generate_lobby_id()));
+        std::fs::write(&path, "word,gloss\n手紙,letter; note\n手紙,\"note, memo\"\n日本,Japan\n").unwrap();
+        let glosses = load_glosses(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(glosses["手紙"], vec!["letter", "note", "note, memo"]);
+        assert_eq!(glosses["日本"], vec!["Japan"]);
+    }
+
+    #[test]
+    fn test_load_dictionary_reads_entry_columns() {
+        let path = std::env::temp_dir().join(format!("dictionary_{}.csv", generate_lobby_id()));
+        std::fs::write(&path, "word,readings,pos,frequency_rank\n日本,にほん;にっぽん,n,42\n日本,,,30\n炎,ほのお\n").unwrap();
+        let mut dictionary = load_dictionary(path.to_str().unwrap(), &data::WordFilter::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut n5 = HashMap::new();
+        n5.insert("日本".to_string(), vec!["にほん".to_string()]);
+        n5.insert("手紙".to_string(), vec!["てがみ".to_string()]);
+        let mut levels = vec![HashMap::new(); 4];
+        levels.push(n5);
+        dictionary.attach_word_levels(&levels);
+
+        assert_eq!(dictionary.len(), 2);
+        let nihon = dictionary.get("日本").unwrap();
+        assert_eq!(nihon.readings, vec!["にほん", "にっぽん"]);
+        assert_eq!(nihon.parts_of_speech, vec!["n"]);
+        assert_eq!(nihon.frequency_rank, Some(30));
+        assert_eq!(nihon.jlpt_level, Some(5));
+        assert_eq!(dictionary.get("炎").unwrap().frequency_rank, None);
+        // Level lists only enrich words the dictionary already has
+        assert!(!dictionary.contains("手紙"));
+    }
+
+    #[test]
+    fn test_word_filter_catches_symbols_tags_and_denylist() {
+        let path = std::env::temp_dir().join(format!("dictionary_{}.csv", generate_lobby_id()));
+        std::fs::write(&path, "word,readings,pos,frequency_rank,tags\n日本,にほん\nＡＢＣ順,えーびーしーじゅん\nＮ響,えぬきょう\n古今,ここん,,,arch\n仝,どう\n々,,,,\n").unwrap();
+        let filter = data::WordFilter {
+            japanese_only: true,
+            tags: data::DEFAULT_FILTER_TAGS.iter().map(|t| t.to_string()).collect(),
+            denylist: HashSet::from(["仝".to_string()]),
+        };
+        let dictionary = load_dictionary(path.to_str().unwrap(), &filter).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let filtered = |w: &str| dictionary.get(w).unwrap().filtered;
+        assert!(!filtered("日本"));
+        assert!(!filtered("々"));
+        assert!(filtered("ＡＢＣ順"));
+        assert!(filtered("Ｎ響"));
+        assert!(filtered("古今"));
+        assert!(filtered("仝"));
+        assert!(dictionary.lookup("古今", true).is_some());
+        assert!(dictionary.lookup("古今", false).is_none());
+    }
+
+    #[test]
+    fn test_jmdict_tags_describe_every_sense() {
+        use dictionary::{Dictionary, JmdictDictionary};
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE JMdict [
+<!ENTITY arch "archaic">
+<!ENTITY oK "word containing out-dated kanji or kanji usage">
+]>
+<JMdict>
+<entry>
+<k_ele><keb>今日</keb></k_ele>
+<k_ele><keb>今E</keb></k_ele>
+<r_ele><reb>きょう</reb></r_ele>
+<sense><gloss>today</gloss></sense>
+<sense><misc>&arch;</misc><gloss>this day (old)</gloss></sense>
+</entry>
+<entry>
+<k_ele><keb>古今</keb></k_ele>
+<k_ele><keb>古个</keb><ke_inf>&oK;</ke_inf></k_ele>
+<r_ele><reb>ここん</reb></r_ele>
+<sense><misc>&arch;</misc><gloss>ancient and modern times</gloss></sense>
+</entry>
+</JMdict>
+"#;
+        let path = std::env::temp_dir().join(format!("jmdict_{}.xml", generate_lobby_id()));
+        std::fs::write(&path, xml).unwrap();
+        let filter = data::WordFilter { japanese_only: true, tags: vec!["oK".to_string()], ..Default::default() };
+        let contents = JmdictDictionary { path: path.clone(), filter }.load().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let words = &contents.words;
+        assert!(words.get("今日").unwrap().tags.is_empty());
+        assert!(!words.get("今日").unwrap().filtered);
+        assert!(words.get("今E").unwrap().filtered);
+        assert_eq!(words.get("古今").unwrap().tags, vec!["arch"]);
+        assert!(!words.get("古今").unwrap().filtered);
+        assert_eq!(words.get("古个").unwrap().tags, vec!["oK", "arch"]);
+        assert!(words.get("古个").unwrap().filtered);
+    }
+
+    #[test]
+    fn test_jmdict_dictionary_reads_entries() {
+        use dictionary::{Dictionary, JmdictDictionary};
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE JMdict [
+<!ENTITY n "noun (common) (futsuumeishi)">
+<!ENTITY adj-na "adjectival nouns or quasi-adjectives (keiyodoshi)">
+<!ENTITY exp "expressions (phrases, clauses, etc.)">
+]>
+<JMdict>
+<entry>
+<ent_seq>1</ent_seq>
+<k_ele><keb>日本</keb><ke_pri>news1</ke_pri><ke_pri>nf01</ke_pri></k_ele>
+<k_ele><keb>日夲</keb></k_ele>
+<r_ele><reb>にほん</reb><re_pri>news1</re_pri></r_ele>
+<r_ele><reb>にっぽん</reb><re_restr>日本</re_restr></r_ele>
+<r_ele><reb>ジャパン</reb><re_nokanji/></r_ele>
+<sense><pos>&n;</pos><gloss>Japan</gloss><gloss xml:lang="ger">Japan (Land)</gloss></sense>
+</entry>
+<entry>
+<ent_seq>2</ent_seq>
+<r_ele><reb>ありがとう</reb></r_ele>
+<sense><pos>&exp;</pos><gloss>thank you</gloss><gloss>thanks &amp; regards</gloss></sense>
+</entry>
+</JMdict>
+"#;
+        let path = std::env::temp_dir().join(format!("jmdict_{}.xml", generate_lobby_id()));
+        std::fs::write(&path, xml).unwrap();
+        let contents = JmdictDictionary { path: path.clone(), filter: data::WordFilter::default() }.load().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents.words.len(), 2);
+        let nihon = contents.words.get("日本").unwrap();
+        assert_eq!(nihon.readings, vec!["にほん", "にっぽん"]);
+        assert_eq!(nihon.parts_of_speech, vec!["n"]);
+        assert_eq!(nihon.frequency_rank, Some(1));
+        let variant = contents.words.get("日夲").unwrap();
+        assert_eq!(variant.readings, vec!["にほん"]);
+        assert_eq!(variant.frequency_rank, None);
+
+        assert_eq!(contents.glosses["日本"], vec!["Japan"]);
+        assert_eq!(contents.glosses["ありがとう"], vec!["thank you", "thanks & regards"]);
+    }
+
+    #[test]
+    fn test_kanji_index_orders_words_by_commonness() {
+        let mut dictionary = data::DictData::from_iter(["日記", "月曜日", "日本", "炎"]);
+        assert_eq!(dictionary.words_with_kanji('日').map(|e| e.word.as_str()).collect::<Vec<_>>(), vec!["日記", "月曜日", "日本"]);
+
+        // Merging in a rank moves a word up its kanji's lists
+        dictionary.insert(data::DictEntry { word: "日本".to_string(), frequency_rank: Some(42), ..Default::default() });
+        dictionary.insert(data::DictEntry { word: "月曜日".to_string(), jlpt_level: Some(5), ..Default::default() });
+        assert_eq!(dictionary.words_with_kanji('日').map(|e| e.word.as_str()).collect::<Vec<_>>(), vec!["日本", "月曜日", "日記"]);
+        assert_eq!(dictionary.words_with_all_kanji(&['月', '日']).map(|e| e.word.as_str()).collect::<Vec<_>>(), vec!["月曜日"]);
+        assert_eq!(dictionary.words_with_kanji('水').count(), 0);
+    }
+
+    #[test]
+    fn test_word_checked_describes_answer_and_suggestions() {
+        let (mut lobby, leader) = setup_deathmatch_playing();
+        let mut dictionary = data::DictData::from_iter(["日本", "日記", "炎"]);
+        dictionary.insert(data::DictEntry { word: "日記".to_string(), frequency_rank: Some(900), ..Default::default() });
+        dictionary.insert(data::DictEntry { word: "日本".to_string(), frequency_rank: Some(42), ..Default::default() });
+        dictionary.attach_word_levels(&lobby.word_list);
+        lobby.dict_list = Arc::new(dictionary);
+        let mut rx = lobby.tx.subscribe();
+
+        let mut next_check = || loop {
+            let msg: shared::ServerMessage = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+            if let shared::ServerMessage::WordChecked { result, .. } = msg {
+                return result;
+            }
+        };
+
+        lobby.process_guess(&leader, "炎").unwrap();
+        let miss = next_check();
+        assert_eq!(miss.accepted, None);
+        let suggestions: Vec<_> = miss.suggestions.unwrap().into_iter().map(|w| w.word).collect();
+        assert_eq!(suggestions, vec!["日本", "日記"]);
+        assert!(miss.error_details.unwrap()[0].contains("にほん"));
+
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Kanji { character: "日".to_string() }));
+        lobby.process_guess(&leader, "日本").unwrap();
+        let hit = next_check().accepted.unwrap();
+        assert_eq!(hit.word, "日本");
+        assert_eq!(hit.readings, vec!["にほん", "にっぽん"]);
+        assert_eq!(hit.frequency_rank, Some(42));
+    }
+
+    #[test]
+    fn test_compound_prompt_requires_both_kanji() {
+        let (lobby, leader) = setup_deathmatch_playing();
+        lobby.current_prompt.write(|p| *p = Some(ActivePrompt::Compound {
+            characters: vec!["日".to_string(), "月".to_string()],
+        }));
+
+        lobby.process_guess(&leader, "日本").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 0);
+
+        lobby.process_guess(&leader, "月曜日").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_compound_prompt_only_picks_playable_pairs() {
+        let (lobby, _leader) = setup_deathmatch_playing();
+        lobby.settings.write(|s| s.content_mode = shared::ContentMode::Compound);
+
+        for _ in 0..10 {
+            lobby.generate_random_prompt(false, false).unwrap();
+            let Some(ActivePrompt::Compound { characters }) = lobby.current_prompt.read(|p| p.clone()) else {
+                panic!("expected a compound prompt");
+            };
+            assert_eq!(characters.len(), 2);
+            assert!(lobby.dict_list.words().any(|w| characters.iter().all(|c| w.contains(c.as_str()))));
+        }
+    }
+
+    #[test]
+    fn test_kanji_reading_accepts_any_listed_reading() {
+        let (lobby, leader) = setup_deathmatch_playing();
+        let set_prompt = || lobby.current_prompt.write(|p| *p = Some(ActivePrompt::KanjiReading {
+            character: "日".to_string(),
+            readings: vec!["ニチ".to_string(), "ジツ".to_string(), "ひ".to_string(), "-び".to_string()],
+        }));
+
+        set_prompt();
+        lobby.process_guess(&leader, "げつ").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 0);
+
+        // On'yomi can be typed in hiragana, affix markers are ignored
+        lobby.process_guess(&leader, "にち").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+        set_prompt();
+        lobby.process_guess(&leader, "び").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_kanji_reading_follows_strictness() {
+        let lobby = create_test_lobby();
+        let prompt = ActivePrompt::KanjiReading { character: "日".to_string(), readings: vec!["ニチ".to_string(), "ねえ".to_string()] };
+        let check = |input: &str, strictness| {
+            utils::check_prompt(&prompt, input, &lobby.dict_list, &lobby.reading_list, strictness, true, false).is_some()
+        };
+
+        // Strict takes readings as they're normalised, in hiragana
+        assert!(check("にち", shared::AnswerStrictness::Strict));
+        assert!(!check("ニチ", shared::AnswerStrictness::Strict));
+        assert!(check("ニチ", shared::AnswerStrictness::Standard));
+        // Only Lenient lets a long-vowel mark stand in for the vowel
+        assert!(!check("ねー", shared::AnswerStrictness::Standard));
+        assert!(check("ねー", shared::AnswerStrictness::Lenient));
+    }
+
+    #[test]
+    fn test_kanji_reading_prompt_respects_reading_type() {
+        let (lobby, _leader) = setup_deathmatch_playing();
+        lobby.settings.write(|s| {
+            s.content_mode = shared::ContentMode::KanjiReading;
+            s.reading_type = shared::ReadingType::Kunyomi;
+        });
+
+        for _ in 0..10 {
+            lobby.generate_random_prompt(false, false).unwrap();
+            let Some(ActivePrompt::KanjiReading { character, readings }) = lobby.current_prompt.read(|p| p.clone()) else {
+                panic!("expected a kanji reading prompt");
+            };
+            // Only 日 and 月 have readings in the fixture
+            match character.as_str() {
+                "日" => assert_eq!(readings, vec!["ひ", "-び", "-か"]),
+                "月" => assert_eq!(readings, vec!["つき"]),
+                other => panic!("{} has no readings", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_unplayable_kanji_keeps_levels() {
+        let lobby = create_test_lobby();
+        let mut levels = (*lobby.kanji_list).clone();
+        levels.push(vec![Kanji { kanji: "炎".to_string(), ..Default::default() }]);
+
+        let hard = data::split_unplayable_kanji(&mut levels, &lobby.dict_list, 2);
+        assert_eq!(levels[0].iter().map(|k| k.kanji.as_str()).collect::<Vec<_>>(), vec!["日"]);
+        assert!(hard[0].iter().any(|k| k.kanji == "月"));
+        assert_eq!(hard[0].len(), 9);
+        // A level can end up empty, but it keeps its place
+        assert!(levels[1].is_empty());
+        assert_eq!(hard[1].len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_vocab_favours_common_words() {
+        let lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        lobby.settings.write(|s| {
+            s.difficulty_levels = vec![DifficultySet::Jlpt(1)];
+            s.content_mode = shared::ContentMode::Vocab;
+            s.weighted = true;
+        });
+        lobby.start_game(&leader).unwrap();
+        assert!(lobby.vocab_weights.read(|w| w.contains_key(&0)));
+
+        // 日本 is ranked first and the seven unranked words share a small weight
+        let common = (0..200)
+            .filter(|_| lobby.generate_random_prompt(false, false).unwrap() == "日本")
+            .count();
+        assert!(common > 100, "日本 came up {} times", common);
+    }
+
+    #[test]
+    fn test_start_game_rejects_levels_emptied_into_hard_pool() {
+        let mut lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        let mut levels = (*lobby.kanji_list).clone();
+        levels.resize(5, Vec::new());
+        let hard = data::split_unplayable_kanji(&mut levels, &lobby.dict_list, 100);
+        assert!(levels.iter().all(Vec::is_empty));
+        lobby.kanji_list = Arc::new(levels);
+        lobby.hard_kanji_list = Arc::new(hard);
+
+        lobby.settings.write(|s| s.difficulty_levels = vec![DifficultySet::Jlpt(1)]);
+        assert!(lobby.start_game(&leader).is_err());
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Lobby);
+
+        lobby.settings.write(|s| s.mode = shared::GameMode::Survival);
+        assert!(lobby.start_game(&leader).is_err());
+    }
+
+    #[test]
+    fn test_load_kanjidic2_reads_details() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<kanjidic2>
+<character>
+<literal>日</literal>
+<radical><rad_value rad_type="classical">72</rad_value><rad_value rad_type="nelson_c">72</rad_value></radical>
+<misc><grade>1</grade><stroke_count>4</stroke_count><stroke_count>5</stroke_count><freq>1</freq><jlpt>4</jlpt></misc>
+<reading_meaning><rmgroup>
+<reading r_type="pinyin">ri4</reading>
+<reading r_type="ja_on">ニチ</reading><reading r_type="ja_on">ジツ</reading>
+<reading r_type="ja_kun">ひ</reading><reading r_type="ja_kun">-び</reading>
+<meaning>day</meaning><meaning>sun</meaning><meaning m_lang="fr">jour</meaning>
+</rmgroup><nanori>あ</nanori></reading_meaning>
+</character>
+</kanjidic2>
+"#;
+        let path = std::env::temp_dir().join(format!("kanjidic2_{}.xml", generate_lobby_id()));
+        std::fs::write(&path, xml).unwrap();
+        let kanjidic = load_kanjidic2(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let entry = &kanjidic["日"];
+        assert_eq!(entry.readings.on, vec!["ニチ", "ジツ"]);
+        assert_eq!(entry.readings.kun, vec!["ひ", "-び"]);
+        assert_eq!(entry.details, KanjiDetails {
+            meanings: vec!["day".to_string(), "sun".to_string()],
+            stroke_count: Some(4),
+            grade: Some(1),
+            radical: Some(72),
+        });
+    }
+
+    #[test]
+    fn test_kanji_details_sent_with_prompts_and_misses() {
+        let (lobby, leader) = setup_deathmatch_playing();
+        let shown = lobby.get_current_prompt_kanji();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].meanings, vec!["day", "sun", "Japan"]);
+        assert_eq!(shown[0].facts(), "4 strokes · grade 1 · radical 72");
+
+        let mut rx = lobby.tx.subscribe();
+        lobby.process_guess(&leader, "炎").unwrap();
+        let missed = loop {
+            let msg: shared::ServerMessage = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+            if let shared::ServerMessage::WordChecked { result, .. } = msg {
+                break result.missed_kanji;
+            }
+        };
+        assert_eq!(missed.len(), 1);
+        assert_eq!(missed[0].on_readings, vec!["ニチ", "ジツ"]);
+
+        // A reading prompt keeps its readings to itself until it's missed
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::KanjiReading {
+            character: "日".to_string(),
+            readings: vec!["にち".to_string()],
+        }));
+        assert!(lobby.get_current_prompt_kanji().is_empty());
+
+        // Kanji KANJIDIC had nothing on don't get an empty panel
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Kanji { character: "屈".to_string() }));
+        assert!(lobby.get_current_prompt_kanji().is_empty());
+    }
+
+    #[test]
+    fn test_load_kanjidic_splits_on_and_kun() {
+        let path = std::env::temp_dir().join(format!("kanjidic_{}.txt", generate_lobby_id()));
+        std::fs::write(&path, "# comment\n日 467C U65e5 B72 G1 S4 ニチ ジツ ひ -び -か T1 あ あき {day} {sun}\n").unwrap();
+        let kanjidic = load_kanjidic(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(kanjidic["日"].on, vec!["ニチ", "ジツ"]);
+        assert_eq!(kanjidic["日"].kun, vec!["ひ", "-び", "-か"]);
+    }
+
+    #[tokio::test]
+    async fn test_time_attack_runs_on_one_clock() {
+        let lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        lobby.settings.write(|s| {
+            s.mode = shared::GameMode::TimeAttack;
+            s.difficulty_levels = vec![DifficultySet::Jlpt(1)];
+            s.time_limit_seconds = Some(30);
+        });
+        lobby.start_game(&leader).unwrap();
+
+        let deadline = lobby.timer_expires_at.read(|t| *t).expect("run clock should be set");
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Kanji { character: "日".to_string() }));
+        lobby.process_guess(&leader, "日本").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+        // A new prompt does not restart the clock
+        assert_eq!(lobby.timer_expires_at.read(|t| *t), Some(deadline));
+
+        // A stale deadline from an earlier run is ignored
+        lobby.finish_time_attack(deadline - 1);
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Playing);
+
+        lobby.finish_time_attack(deadline);
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Finished);
+    }
+
+    #[tokio::test]
+    async fn test_solo_modes_need_exactly_one_player() {
+        let lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        let guest = PlayerId::from("guest");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        lobby.add_player(guest.clone(), "Guest".to_string()).unwrap();
+
+        for mode in [shared::GameMode::TimeAttack, shared::GameMode::Daily, shared::GameMode::Review] {
+            lobby.settings.write(|s| s.mode = mode);
+            assert!(lobby.start_game(&leader).is_err(), "{:?} started with two players", mode);
+            assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Lobby);
+        }
+
+        lobby.remove_player(&guest);
+
+        // Bots would answer the solo player's prompts, so they can't be added or brought along
+        lobby.settings.write(|s| s.mode = shared::GameMode::TimeAttack);
+        assert!(lobby.add_bot(&leader, perfect_bot()).is_err());
+        lobby.settings.write(|s| s.mode = shared::GameMode::Deathmatch);
+        lobby.add_bot(&leader, perfect_bot()).unwrap();
+        for mode in [shared::GameMode::TimeAttack, shared::GameMode::Daily, shared::GameMode::Review] {
+            lobby.settings.write(|s| s.mode = mode);
+            assert!(lobby.start_game(&leader).is_err(), "{:?} started with a bot", mode);
+        }
+
+        let bot = lobby.get_all_players().into_iter().find(|p| p.is_bot).unwrap().id;
+        lobby.remove_player(&bot);
+        lobby.settings.write(|s| s.mode = shared::GameMode::TimeAttack);
+        lobby.start_game(&leader).unwrap();
+    }
+
+    #[test]
+    fn test_difficulty_sets_parse_by_id() {
+        for set in DifficultySet::ALL {
+            assert_eq!(set.to_string().parse::<DifficultySet>(), Ok(set));
+        }
+        assert_eq!(serde_json::to_string(&DifficultySet::Grade(3)).unwrap(), "\"G3\"");
+        assert_eq!(serde_json::from_str::<DifficultySet>("\"N5\"").unwrap(), DifficultySet::Jlpt(5));
+        assert_eq!(DifficultySet::Joyo.label(), "Jōyō");
+        assert!("N6".parse::<DifficultySet>().is_err());
+        assert!("G0".parse::<DifficultySet>().is_err());
+    }
+
+    #[test]
+    fn test_load_joyo_kanji_and_grades() {
+        let joyo_path = std::env::temp_dir().join(format!("joyo_{}.csv", generate_lobby_id()));
+        std::fs::write(&joyo_path, "亜\n日\n月\n").unwrap();
+        let levels = vec![vec![
+            Kanji { kanji: "日".to_string(), frequency: 1, ..Default::default() },
+            Kanji { kanji: "月".to_string(), frequency: 20, ..Default::default() },
+        ]];
+        let mut joyo = data::load_joyo_kanji(joyo_path.to_str().unwrap(), &levels).unwrap();
+        std::fs::remove_file(&joyo_path).unwrap();
+
+        // The list has no header, so its first kanji is kept
+        assert_eq!(joyo.iter().map(|k| k.kanji.as_str()).collect::<Vec<_>>(), vec!["亜", "日", "月"]);
+        assert_eq!(joyo[1].frequency, 1);
+        // Kanji missing from the JLPT lists rank just past the rarest one, so weighted games still draw them
+        assert_eq!(joyo[0].frequency, 21);
+
+        joyo[1].details.meanings = vec!["day".to_string()];
+        let kyoiku_path = std::env::temp_dir().join(format!("kyoiku_{}.csv", generate_lobby_id()));
+        std::fs::write(&kyoiku_path, "日,1\n月,1\n亜,7\n").unwrap();
+        let grades = data::load_kyoiku_kanji(kyoiku_path.to_str().unwrap(), &joyo).unwrap();
+        std::fs::remove_file(&kyoiku_path).unwrap();
+        assert_eq!(grades.len(), 6);
+        assert_eq!(grades[0].iter().map(|k| k.kanji.as_str()).collect::<Vec<_>>(), vec!["日", "月"]);
+        assert_eq!(grades[0][0].details.meanings, vec!["day"]);
+        assert_eq!(grades[0][1].frequency, 20);
+        assert!(grades[1..].iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    fn test_start_game_combines_difficulty_sets() {
+        let mut lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        let mut sets = vec![Vec::new(); DifficultySet::ALL.len()];
+        sets[0] = lobby.kanji_list[0].clone();
+        let grade_one = DifficultySet::Grade(1).index().unwrap();
+        sets[grade_one] = vec![Kanji { kanji: "月".to_string(), frequency: 1, ..Default::default() }];
+        lobby.kanji_list = Arc::new(sets);
+
+        // An empty set is an error rather than a quiet switch to another set
+        lobby.settings.write(|s| s.difficulty_levels = vec![DifficultySet::Grade(1), DifficultySet::Joyo]);
+        assert!(lobby.start_game(&leader).is_err());
+
+        lobby.settings.write(|s| s.difficulty_levels = vec![DifficultySet::Jlpt(1), DifficultySet::Grade(1)]);
+        lobby.start_game(&leader).unwrap();
+        assert_eq!(lobby.active_level_indices.read(|i| i.clone()), vec![0, grade_one]);
+
+        // Word-based modes need a JLPT level
+        lobby.game_status.write(|s| *s = GameStatus::Lobby);
+        lobby.settings.write(|s| {
+            s.difficulty_levels = vec![DifficultySet::Grade(1)];
+            s.content_mode = shared::ContentMode::Vocab;
+        });
+        assert!(lobby.start_game(&leader).is_err());
+    }
+
+    #[test]
+    fn test_time_attack_key_ignores_level_order() {
+        use models::TimeAttackKey;
+        let a = TimeAttackKey::new(&[DifficultySet::Jlpt(5), DifficultySet::Jlpt(4)], &shared::ContentMode::Kanji, 60);
+        let b = TimeAttackKey::new(&[DifficultySet::Jlpt(4), DifficultySet::Jlpt(5), DifficultySet::Jlpt(5)], &shared::ContentMode::Kanji, 60);
+        assert_eq!(a, b);
+        assert_eq!(a.level_set, "N4,N5");
+        assert_ne!(a, TimeAttackKey::new(&[DifficultySet::Jlpt(4), DifficultySet::Jlpt(5)], &shared::ContentMode::Vocab, 60));
+    }
+
+    fn start_daily_challenge() -> (LobbyState, PlayerId) {
+        let mut lobby = create_test_lobby();
+        // The daily challenge always plays every JLPT level
+        let level = lobby.kanji_list[0].clone();
+        lobby.kanji_list = Arc::new(vec![level; 5]);
+        let leader = PlayerId::from("leader");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        lobby.settings.write(|s| {
+            s.mode = shared::GameMode::Daily;
+            s.content_mode = shared::ContentMode::Vocab;
+            s.time_limit_seconds = Some(5);
+        });
+        lobby.start_game(&leader).unwrap();
+        (lobby, leader)
+    }
+
+    #[tokio::test]
+    async fn test_daily_challenge_prompts_match_across_lobbies() {
+        let (first, _) = start_daily_challenge();
+        let (second, _) = start_daily_challenge();
+
+        let sequence = |lobby: &LobbyState| -> Vec<String> {
+            let mut prompts = vec![lobby.get_current_prompt_text().unwrap()];
+            for _ in 0..10 {
+                prompts.push(lobby.generate_random_prompt(false, true).unwrap());
+            }
+            prompts
+        };
+        assert_eq!(sequence(&first), sequence(&second));
+    }
+
+    #[tokio::test]
+    async fn test_daily_challenge_uses_fixed_rules() {
+        let (lobby, _) = start_daily_challenge();
+        let settings = lobby.settings.read(|s| s.clone());
+        assert_eq!(settings.content_mode, shared::ContentMode::Kanji);
+        assert_eq!(settings.difficulty_levels.len(), 5);
+        assert_eq!(settings.timer_seconds(), Some(shared::DEFAULT_TIME_ATTACK_SECONDS));
+        assert!(lobby.daily_date.read(|d| d.is_some()));
+        assert!(lobby.timer_expires_at.read(|t| t.is_some()));
+    }
+
+    #[test]
+    fn test_daily_challenge_pins_answer_rules() {
+        let lenient = GameSettings {
+            answer_strictness: shared::AnswerStrictness::Lenient,
+            allow_romaji: true,
+            exact_headwords: true,
+            full_dictionary: true,
+            reading_type: shared::ReadingType::Kunyomi,
+            ..Default::default()
+        };
+        let strict = GameSettings { answer_strictness: shared::AnswerStrictness::Strict, ..Default::default() };
+        let (lenient, strict) = (lenient.daily_challenge(), strict.daily_challenge());
+        assert_eq!(lenient, strict);
+
+        let canonical = GameSettings::default();
+        assert_eq!(lenient.answer_strictness, canonical.answer_strictness);
+        assert_eq!(lenient.allow_romaji, canonical.allow_romaji);
+        assert_eq!(lenient.exact_headwords, canonical.exact_headwords);
+        assert_eq!(lenient.full_dictionary, canonical.full_dictionary);
+        assert_eq!(lenient.reading_type, canonical.reading_type);
+    }
+
+    #[test]
+    fn test_daily_challenge_seed_changes_each_day() {
+        let day = chrono::NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        assert_eq!(utils::daily_challenge_seed(day), utils::daily_challenge_seed(day));
+        assert_ne!(utils::daily_challenge_seed(day), utils::daily_challenge_seed(day.succ_opt().unwrap()));
+    }
+
+    fn entrant(name: &str) -> shared::BracketEntrant {
+        shared::BracketEntrant { id: PlayerId::from(name), name: name.to_string() }
+    }
+
+    #[test]
+    fn test_seed_bracket_gives_byes_a_free_pass() {
+        let entrants = ["a", "b", "c", "d", "e"].map(entrant).to_vec();
+        let bracket = tournament::seed_bracket(LobbyId::from("CUP"), entrants);
+
+        assert_eq!(bracket.rounds.iter().map(|r| r.len()).collect::<Vec<_>>(), vec![4, 2, 1]);
+        // Three byes: a, b and c go straight through, d plays e
+        assert_eq!(bracket.rounds[1][0].players, [Some(entrant("a")), Some(entrant("b"))]);
+        assert_eq!(bracket.rounds[1][1].players, [Some(entrant("c")), None]);
+        assert_eq!(bracket.rounds[0][3].players, [Some(entrant("d")), Some(entrant("e"))]);
+        assert!(bracket.rounds[0][3].winner.is_none());
+        assert!(bracket.is_running());
+    }
+
+    #[test]
+    fn test_record_winner_crowns_champion() {
+        let mut bracket = tournament::seed_bracket(LobbyId::from("CUP"), ["a", "b"].map(entrant).to_vec());
+        tournament::record_winner(&mut bracket, 0, 0, PlayerId::from("b"));
+        assert_eq!(bracket.champion(), Some(&entrant("b")));
+        assert!(!bracket.is_running());
+    }
+
+    /// Polls `check` until it holds, giving spawned tournament tasks time to run.
+    async fn eventually(check: impl Fn() -> bool) {
+        for _ in 0..100 {
+            if check() {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("condition never held");
+    }
+
+    #[tokio::test]
+    async fn test_tournament_advances_winners_to_final() {
+        let app_state = AppState::create().expect("Failed to create AppState");
+        let host_id = LobbyId::from("CUP");
+        let host = Arc::new(create_test_lobby());
+        for name in ["a", "b", "c"] {
+            host.add_player(PlayerId::from(name), name.to_string()).unwrap();
+        }
+        host.settings.write(|s| s.mode = shared::GameMode::Tournament);
+        app_state.lobbies.write(|l| { l.insert(host_id.clone(), Arc::clone(&host)); });
+
+        assert!(app_state.start_tournament(&host_id, &host, &PlayerId::from("b")).await.is_err());
+        app_state.start_tournament(&host_id, &host, &PlayerId::from("a")).await.unwrap();
+
+        // One bye, so a single first-round match is underway
+        let bracket = host.bracket.read(|b| b.clone()).unwrap();
+        let first = bracket.rounds[0].iter().find(|m| m.lobby_id.is_some()).unwrap().clone();
+        let match_lobby = app_state.get_lobby(first.lobby_id.as_ref().unwrap()).unwrap();
+        assert_eq!(match_lobby.settings.read(|s| s.mode), shared::GameMode::Duel);
+        assert_eq!(match_lobby.game_status.read(|s| *s), GameStatus::Playing);
+        assert_eq!(match_lobby.get_lobby_info(&host_id).bracket, Some(bracket.clone()));
+
+        // Leaving mid-match hands the opponent a walkover into the final
+        let [Some(quitter), Some(survivor)] = first.players.clone() else { panic!("match should be full") };
+        match_lobby.remove_player(&quitter.id);
+        eventually(|| host.bracket.read(|b| b.as_ref().unwrap().rounds[1][0].lobby_id.is_some())).await;
+
+        let final_match = host.bracket.read(|b| b.as_ref().unwrap().rounds[1][0].clone());
+        assert!(final_match.players.contains(&Some(survivor.clone())));
+        let final_lobby = app_state.get_lobby(final_match.lobby_id.as_ref().unwrap()).unwrap();
+
+        // The final is decided when the Duel ends
+        final_lobby.players.write(|players| {
+            for p in players.iter_mut().filter(|p| p.id != survivor.id) {
+                p.is_eliminated = true;
+            }
+        });
+        final_lobby.game_status.write(|s| *s = GameStatus::Finished);
+        final_lobby.broadcast(shared::ServerMessage::LeaderUpdate { leader_id: survivor.id.clone() });
+        eventually(|| host.bracket.read(|b| !b.as_ref().unwrap().is_running())).await;
+
+        assert_eq!(host.bracket.read(|b| b.as_ref().unwrap().champion().cloned()), Some(survivor));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_finished_tournament_cleans_up_match_lobbies() {
+        let app_state = AppState::create().expect("Failed to create AppState");
+        let host_id = LobbyId::from("CUP");
+        let host = Arc::new(create_test_lobby());
+        for name in ["a", "b"] {
+            host.add_player(PlayerId::from(name), name.to_string()).unwrap();
+        }
+        host.settings.write(|s| s.mode = shared::GameMode::Tournament);
+        app_state.lobbies.write(|l| { l.insert(host_id.clone(), Arc::clone(&host)); });
+        app_state.start_tournament(&host_id, &host, &PlayerId::from("a")).await.unwrap();
+
+        let match_id = host.bracket.read(|b| b.as_ref().unwrap().rounds[0][0].lobby_id.clone()).unwrap();
+        let match_lobby = app_state.get_lobby(&match_id).unwrap();
+        host.players.write(|ps| ps.iter_mut().for_each(|p| p.is_connected = false));
+        match_lobby.remove_player(&PlayerId::from("b"));
+        drop(match_lobby);
+        eventually(|| host.bracket.read(|b| !b.as_ref().unwrap().is_running())).await;
+
+        tokio::time::sleep(std::time::Duration::from_secs(61)).await;
+        assert!(app_state.get_lobby(&host_id).is_err());
+        assert!(app_state.get_lobby(&match_id).is_err());
+    }
+
+    /// Solo Survival game over five levels that all use the fixture's kanji,
+    /// climbing a level every two correct answers.
+    fn setup_survival_playing(lives: u32) -> (LobbyState, PlayerId) {
+        let mut lobby = create_test_lobby();
+        let level = lobby.kanji_list[0].clone();
+        lobby.kanji_list = Arc::new(vec![level; 5]);
+
+        let leader = PlayerId::from("leader");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        lobby.settings.write(|s| {
+            s.mode = shared::GameMode::Survival;
+            s.survival_streak = Some(2);
+            s.initial_lives = Some(lives);
+        });
+        lobby.start_game(&leader).unwrap();
+        (lobby, leader)
+    }
+
+    fn guess_with_prompt(lobby: &LobbyState, player: &PlayerId, input: &str) {
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Kanji { character: "日".to_string() }));
+        lobby.process_guess(player, input).unwrap();
+    }
+
+    #[test]
+    fn test_survival_climbs_after_streak() {
+        let (lobby, leader) = setup_survival_playing(1);
+        assert_eq!(lobby.get_current_tier().as_deref(), Some("N5"));
+
+        guess_with_prompt(&lobby, &leader, "日本");
+        assert_eq!(lobby.get_current_tier().as_deref(), Some("N5"));
+        guess_with_prompt(&lobby, &leader, "日本");
+        assert_eq!(lobby.get_current_tier().as_deref(), Some("N4"));
+        assert_eq!(lobby.active_level_indices.read(|i| i.clone()), vec![3]);
+
+        // A solo player keeps going until their last life is gone
+        guess_with_prompt(&lobby, &leader, "哀歌");
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Finished);
+    }
+
+    #[test]
+    fn test_survival_miss_resets_streak() {
+        let (lobby, leader) = setup_survival_playing(3);
+        guess_with_prompt(&lobby, &leader, "日本");
+        guess_with_prompt(&lobby, &leader, "哀歌");
+        guess_with_prompt(&lobby, &leader, "日本");
+        assert_eq!(lobby.get_current_tier().as_deref(), Some("N5"));
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Playing);
+        assert_eq!(lobby.get_lobby_info(&LobbyId::from("x")).players[0].lives, Some(2));
+    }
+
+    #[test]
+    fn test_team_deathmatch_pools_scores() {
+        let lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        let mate = PlayerId::from("mate");
+        let rival = PlayerId::from("rival");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        lobby.add_player(mate.clone(), "Mate".to_string()).unwrap();
+        lobby.add_player(rival.clone(), "Rival".to_string()).unwrap();
+        lobby.active_level_indices.write(|i| i.push(0));
+        lobby.game_status.write(|s| *s = GameStatus::Playing);
+        lobby.settings.write(|s| {
+            s.target_score = Some(3);
+            s.teams = vec![
+                shared::Team { name: "Red".to_string(), members: vec![leader.clone(), mate.clone()] },
+                shared::Team { name: "Blue".to_string(), members: vec![rival.clone()] },
+            ];
+        });
+
+        for guesser in [&leader, &mate, &rival] {
+            lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Kanji { character: "日".to_string() }));
+            lobby.process_guess(guesser, "日本").unwrap();
+        }
+        assert_eq!(lobby.get_team_score("Red"), 2);
+        assert_eq!(lobby.get_team_score("Blue"), 1);
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Playing);
+
+        // Red reaches the target of 3 even though no single member has
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Kanji { character: "日".to_string() }));
+        lobby.process_guess(&mate, "日本").unwrap();
+        assert_eq!(lobby.get_player_score(&mate).unwrap(), 2);
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Finished);
+
+        let players = lobby.get_all_players();
+        let team_of = |id: &PlayerId| players.iter().find(|p| &p.id == id).unwrap().team.clone();
+        assert_eq!(team_of(&leader).as_deref(), Some("Red"));
+        assert_eq!(team_of(&rival).as_deref(), Some("Blue"));
+    }
+
+    #[test]
+    fn test_content_modes_follow_loaded_data() {
+        let mut lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        let info = lobby.get_lobby_info(&LobbyId::from("x"));
+        assert!(info.content_modes.contains(&shared::ContentMode::Meaning));
+
+        // Without glosses Meaning isn't offered and can't be chosen
+        lobby.gloss_list = Arc::new(HashMap::new());
+        let info = lobby.get_lobby_info(&LobbyId::from("x"));
+        assert!(!info.content_modes.contains(&shared::ContentMode::Meaning));
+        assert!(info.content_modes.contains(&shared::ContentMode::Kanji));
+        let settings = GameSettings { content_mode: shared::ContentMode::Meaning, ..Default::default() };
+        assert!(lobby.update_settings(&leader, settings).is_err());
+        assert_eq!(lobby.settings.read(|s| s.content_mode.clone()), shared::ContentMode::Kanji);
+
+        // Nor is the reading quiz without kanji readings
+        assert!(info.content_modes.contains(&shared::ContentMode::KanjiReading));
+        let unread: Vec<Vec<Kanji>> = lobby.kanji_list.iter()
+            .map(|level| level.iter().map(|k| Kanji { readings: KanjiReadings::default(), ..k.clone() }).collect())
+            .collect();
+        lobby.kanji_list = Arc::new(unread);
+        let info = lobby.get_lobby_info(&LobbyId::from("x"));
+        assert!(!info.content_modes.contains(&shared::ContentMode::KanjiReading));
+    }
+
+    #[test]
+    fn test_update_settings_rejects_invalid_teams() {
+        let lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+
+        let team = |name: &str| shared::Team { name: name.to_string(), members: vec![leader.clone()] };
+        let settings = GameSettings { teams: vec![team("Red"), team("Blue")], ..Default::default() };
+        assert!(lobby.update_settings(&leader, settings).is_err());
+
+        let settings = GameSettings { teams: vec![team("Red"), shared::Team { name: "Red".to_string(), members: vec![] }], ..Default::default() };
+        assert!(lobby.update_settings(&leader, settings).is_err());
+
+        let settings = GameSettings { teams: vec![team("Red")], ..Default::default() };
+        assert!(lobby.update_settings(&leader, settings).is_ok());
+
+        // Players who aren't in the lobby can't be put on a team
+        let ghost = shared::Team { name: "Blue".to_string(), members: vec![PlayerId::from("ghost")] };
+        let settings = GameSettings { teams: vec![team("Red"), ghost], ..Default::default() };
+        assert!(lobby.update_settings(&leader, settings).is_err());
+    }
+
+    #[test]
+    fn test_leaving_player_is_taken_off_their_team() {
+        let lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        let mate = PlayerId::from("mate");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        lobby.add_player(mate.clone(), "Mate".to_string()).unwrap();
+        let team = shared::Team { name: "Red".to_string(), members: vec![leader.clone(), mate.clone()] };
+        let settings = GameSettings { teams: vec![team], ..Default::default() };
+        lobby.update_settings(&leader, settings).unwrap();
+
+        let mut rx = lobby.tx.subscribe();
+        lobby.remove_player(&mate);
+        assert_eq!(lobby.settings.read(|s| s.teams[0].members.clone()), vec![leader.clone()]);
+        let mut settings_updates = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|msg| match serde_json::from_str::<shared::ServerMessage>(&msg) {
+                Ok(shared::ServerMessage::SettingsUpdate { settings }) => Some(settings),
+                _ => None,
+            });
+        assert_eq!(settings_updates.next().map(|s| s.teams[0].members.len()), Some(1));
+
+        // Rejoining doesn't put them back on the team
+        lobby.add_player(mate.clone(), "Mate".to_string()).unwrap();
+        assert!(lobby.get_all_players().iter().find(|p| p.id == mate).unwrap().team.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_team_game_start_assigns_unteamed_players() {
+        let lobby = create_test_lobby();
+        let leader = PlayerId::from("leader");
+        lobby.add_player(leader.clone(), "Leader".to_string()).unwrap();
+        for name in ["mate", "rival"] {
+            lobby.add_player(PlayerId::from(name), name.to_string()).unwrap();
+        }
+        let teams = vec![
+            shared::Team { name: "Red".to_string(), members: vec![leader.clone()] },
+            shared::Team { name: "Blue".to_string(), members: vec![] },
+        ];
+        lobby.update_settings(&leader, GameSettings { teams, ..lobby.settings.read(|s| s.clone()) }).unwrap();
+
+        lobby.start_game(&leader).unwrap();
+        let players = lobby.get_all_players();
+        let team_of = |id: &str| players.iter().find(|p| p.id == PlayerId::from(id)).unwrap().team.clone();
+        assert_eq!(team_of("leader").as_deref(), Some("Red"));
+        // The smaller team is filled first
+        assert_eq!(team_of("mate").as_deref(), Some("Blue"));
+        assert!(team_of("rival").is_some());
+    }
+
     #[test]
     fn test_duel_wrong_turn_is_ignored() {
         let lobby = create_test_lobby();
@@ -476,6 +1517,615 @@ mod tests {
         assert_eq!(lobby.get_player_score(&p2).unwrap(), 0);
     }
 
+    // ── Bomb ────────────────────────────────────────────────────────────────
+
+    /// Two-player Bomb game where p1 holds 日.
+    fn setup_bomb_playing() -> (LobbyState, PlayerId, PlayerId) {
+        let lobby = create_test_lobby();
+        let p1 = PlayerId::from("p1");
+        let p2 = PlayerId::from("p2");
+        lobby.add_player(p1.clone(), "Alice".to_string()).unwrap();
+        lobby.add_player(p2.clone(), "Bob".to_string()).unwrap();
+        lobby.settings.write(|s| { s.mode = shared::GameMode::Bomb; s.initial_lives = Some(2); });
+        lobby.players.write(|ps| ps.iter_mut().for_each(|p| p.lives = Some(2)));
+        lobby.game_status.write(|s| *s = GameStatus::Playing);
+        lobby.turn_order.write(|o| { o.push(p1.clone()); o.push(p2.clone()); });
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Kanji { character: "日".to_string() }));
+        lobby.active_level_indices.write(|i| i.push(0));
+        (lobby, p1, p2)
+    }
+
+    #[test]
+    fn test_bomb_miss_keeps_bomb_and_answer_passes_it() {
+        let (lobby, p1, p2) = setup_bomb_playing();
+
+        lobby.process_guess(&p1, "日日日").unwrap();
+        lobby.process_guess(&p1, "炎").unwrap();
+        assert_eq!(lobby.get_current_turn_player(), Some(p1.clone()));
+        assert_eq!(lobby.players.read(|ps| ps[0].lives), Some(2));
+
+        lobby.process_guess(&p1, "日本").unwrap();
+        assert_eq!(lobby.get_current_turn_player(), Some(p2));
+        // The fuse is never shown to clients
+        assert_eq!(lobby.timer_expires_at.read(|t| *t), None);
+    }
+
+    #[tokio::test]
+    async fn test_bomb_explosion_costs_holder_a_life() {
+        let (lobby, p1, p2) = setup_bomb_playing();
+        lobby.fuse_generation.write(|g| *g = 1);
+
+        // A fuse from before the last relight does nothing
+        lobby.process_explosion(0);
+        assert_eq!(lobby.players.read(|ps| ps[0].lives), Some(2));
+
+        lobby.process_explosion(1);
+        assert_eq!(lobby.players.read(|ps| ps[0].lives), Some(1));
+        assert_eq!(lobby.get_current_turn_player(), Some(p2.clone()));
+        assert_eq!(lobby.fuse_generation.read(|g| *g), 2);
+
+        // Knocking out one of the last two players ends the game
+        lobby.current_turn_index.write(|i| *i = 0);
+        lobby.process_explosion(2);
+        assert!(lobby.players.read(|ps| ps.iter().any(|p| p.id == p1 && p.is_eliminated)));
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Finished);
+    }
+
+    #[test]
+    fn test_fuse_range_is_ordered_and_nonzero() {
+        let mut settings = GameSettings { fuse_min_seconds: Some(0), fuse_max_seconds: None, ..Default::default() };
+        assert_eq!(settings.fuse_range(), (1, shared::DEFAULT_FUSE_MAX_SECONDS));
+        settings.fuse_min_seconds = Some(40);
+        settings.fuse_max_seconds = Some(20);
+        assert_eq!(settings.fuse_range(), (40, 40));
+    }
+
+    // ── Series ──────────────────────────────────────────────────────────────
+
+    fn setup_duel_series() -> (LobbyState, PlayerId) {
+        let lobby = create_test_lobby();
+        let p1 = PlayerId::from("p1");
+        lobby.add_player(p1.clone(), "Alice".to_string()).unwrap();
+        lobby.add_player(PlayerId::from("p2"), "Bob".to_string()).unwrap();
+        lobby.settings.write(|s| { s.mode = shared::GameMode::Duel; s.initial_lives = Some(1); s.series_length = Some(3); });
+        (lobby, p1)
+    }
+
+    #[tokio::test]
+    async fn test_series_plays_on_until_clinched() {
+        let (lobby, p1) = setup_duel_series();
+        lobby.start_game(&p1).unwrap();
+
+        loop {
+            // With one life, skipping knocks the current player out and ends the game
+            let current = lobby.get_current_turn_player().unwrap();
+            lobby.process_skip(&current).unwrap();
+            assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Finished);
+
+            let series = lobby.series.read(|s| s.clone()).unwrap();
+            if series.champion.is_some() {
+                break;
+            }
+            // A stale request from an earlier game does nothing
+            lobby.start_next_series_game(series.games_played - 1);
+            assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Finished);
+
+            lobby.start_next_series_game(series.games_played);
+            assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Playing);
+        }
+
+        let series = lobby.series.read(|s| s.clone()).unwrap();
+        let champion = series.champion.clone().unwrap();
+        assert_eq!(series.wins_of(&champion), 2);
+        assert!((2..=3).contains(&series.games_played));
+        assert_eq!(lobby.get_lobby_info(&LobbyId::from("x")).series, Some(series.clone()));
+
+        // Once clinched there is no next game, and starting again begins a new series
+        lobby.start_next_series_game(series.games_played);
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Finished);
+        lobby.reset_lobby(&p1).unwrap();
+        lobby.start_game(&p1).unwrap();
+        assert_eq!(lobby.series.read(|s| s.as_ref().map(|s| s.games_played)), Some(0));
+    }
+
+    #[test]
+    fn test_series_ends_after_best_of_games_without_a_majority() {
+        let (a, b, c) = (PlayerId::from("a"), PlayerId::from("b"), PlayerId::from("c"));
+        let mut series = shared::SeriesStandings::new(3);
+        series.record_win(a.clone(), "A".to_string());
+        series.record_win(b.clone(), "B".to_string());
+        assert_eq!(series.champion, None);
+        // Three different winners: the last game's winner is in the tie and takes it
+        series.record_win(c.clone(), "C".to_string());
+        assert_eq!(series.champion, Some(c.clone()));
+
+        let mut series = shared::SeriesStandings::new(5);
+        for winner in [&a, &b, &c, &b, &c] {
+            series.record_win(winner.clone(), String::new());
+        }
+        assert_eq!(series.games_played, 5);
+        assert_eq!(series.champion, Some(c.clone()));
+
+        // Otherwise it goes to whoever of the tied players won a game first
+        let mut series = shared::SeriesStandings::new(5);
+        for winner in [&b, &a, &a, &b, &c] {
+            series.record_win(winner.clone(), String::new());
+        }
+        assert_eq!(series.champion, Some(b));
+    }
+
+    #[tokio::test]
+    async fn test_series_dropped_when_length_changes() {
+        let (lobby, p1) = setup_duel_series();
+        lobby.start_game(&p1).unwrap();
+        lobby.reset_lobby(&p1).unwrap();
+        assert!(lobby.series.read(|s| s.is_some()));
+
+        let settings = GameSettings { series_length: Some(5), ..lobby.settings.read(|s| s.clone()) };
+        lobby.update_settings(&p1, settings).unwrap();
+        assert!(lobby.series.read(|s| s.is_none()));
+    }
+
+    // ── Bots ────────────────────────────────────────────────────────────────
+
+    fn perfect_bot() -> shared::BotProfile {
+        shared::BotProfile { min_delay_ms: 0, max_delay_ms: 0, accuracy: 100, common_bias: 100 }
+    }
+
+    #[test]
+    fn test_bot_answers_are_accepted() {
+        let lobby = create_test_lobby();
+        lobby.used_words.write(|w| { w.insert("日記".to_string()); });
+        let mut rng = rand::rng();
+        let prompts = [
+            ActivePrompt::Kanji { character: "日".to_string() },
+            ActivePrompt::Compound { characters: vec!["月".to_string(), "日".to_string()] },
+            ActivePrompt::KanjiReading { character: "月".to_string(), readings: vec!["ゲツ".to_string(), "つき".to_string()] },
+            ActivePrompt::Vocab { word: "日本".to_string(), readings: vec!["にほん".to_string()] },
+            ActivePrompt::ReverseVocab { reading: "にほん".to_string(), words: vec!["日本".to_string(), "二本".to_string()] },
+            ActivePrompt::Shiritori { word: "日記".to_string(), reading: "にっき".to_string() },
+        ];
+        for prompt in prompts {
+            let answer = bot::pick_answer(&lobby, &prompt, 100, &mut rng).unwrap();
+            assert!(utils::check_prompt(&prompt, &answer, &lobby.dict_list, &lobby.reading_list, shared::AnswerStrictness::Strict, true, false).is_some(), "{:?} -> {}", prompt, answer);
+        }
+        // Nothing in the dictionary has both of these
+        let impossible = ActivePrompt::Compound { characters: vec!["糸".to_string(), "木".to_string()] };
+        assert_eq!(bot::pick_answer(&lobby, &impossible, 0, &mut rng), None);
+    }
+
+    #[test]
+    fn test_bot_skips_filtered_words_in_common_lobbies() {
+        let mut lobby = create_test_lobby();
+        let mut dictionary = (*lobby.dict_list).clone();
+        let filter = data::WordFilter { denylist: HashSet::from(["日本".to_string()]), ..Default::default() };
+        filter.apply(&mut dictionary);
+        lobby.dict_list = Arc::new(dictionary);
+        let mut rng = rand::rng();
+        let prompt = ActivePrompt::Kanji { character: "日".to_string() };
+
+        for _ in 0..20 {
+            let answer = bot::pick_answer(&lobby, &prompt, 0, &mut rng).unwrap();
+            assert_ne!(answer, "日本");
+            assert!(utils::check_prompt(&prompt, &answer, &lobby.dict_list, &lobby.reading_list, shared::AnswerStrictness::Strict, true, false).is_some());
+        }
+        // The only word with both kanji is filtered, so there's nothing to say
+        let compound = ActivePrompt::Compound { characters: vec!["日".to_string(), "本".to_string()] };
+        assert_eq!(bot::pick_answer(&lobby, &compound, 0, &mut rng), None);
+
+        lobby.settings.write(|s| s.full_dictionary = true);
+        assert_eq!(bot::pick_answer(&lobby, &compound, 0, &mut rng).as_deref(), Some("日本"));
+    }
+
+    #[test]
+    fn test_add_bot_rules() {
+        let lobby = create_test_lobby();
+        let p1 = PlayerId::from("p1");
+        lobby.add_player(p1.clone(), "Alice".to_string()).unwrap();
+        lobby.add_player(PlayerId::from("p2"), "Bob".to_string()).unwrap();
+
+        assert!(matches!(lobby.add_bot(&PlayerId::from("p2"), perfect_bot()), Err(AppError::AuthError(_))));
+        let bot_id = lobby.add_bot(&p1, perfect_bot()).unwrap();
+        assert!(lobby.get_all_players().iter().any(|p| p.id == bot_id && p.is_bot && p.name == "Bot 1"));
+        assert!(lobby.promote_leader(&p1, &bot_id).is_err());
+
+        // Leadership passes to a human, and a lobby with only bots left is empty
+        lobby.remove_player(&p1);
+        assert_eq!(lobby.lobby_leader.read(|l| l.clone()), PlayerId::from("p2"));
+        lobby.set_player_connected(&PlayerId::from("p2"), false);
+        assert!(lobby.all_disconnected());
+        lobby.remove_player(&PlayerId::from("p2"));
+        assert!(lobby.has_no_humans());
+    }
+
+    #[tokio::test]
+    async fn test_bot_waits_for_its_duel_turn() {
+        let lobby = Arc::new(create_test_lobby());
+        let p1 = PlayerId::from("p1");
+        lobby.add_player(p1.clone(), "Alice".to_string()).unwrap();
+        let bot_id = lobby.add_bot(&p1, perfect_bot()).unwrap();
+        bot::spawn_bot(&lobby, bot_id.clone(), perfect_bot());
+        lobby.settings.write(|s| { s.mode = shared::GameMode::Duel; s.initial_lives = Some(3); });
+
+        lobby.start_game(&p1).unwrap();
+        lobby.turn_order.write(|o| *o = vec![p1.clone(), bot_id.clone()]);
+        lobby.current_turn_index.write(|i| *i = 0);
+
+        tokio::time::sleep(std::time::Duration::from_millis(600)).await;
+        assert_eq!(lobby.get_current_turn_player(), Some(p1.clone()));
+
+        // Once it's the bot's turn it answers correctly and hands the turn back
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Kanji { character: "日".to_string() }));
+        lobby.current_turn_index.write(|i| *i = 1);
+        lobby.broadcast(shared::ServerMessage::PlayerListUpdate { players: lobby.get_all_players() });
+        for _ in 0..300 {
+            if lobby.get_player_score(&bot_id).unwrap() == 1 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(lobby.get_player_score(&bot_id).unwrap(), 1);
+        assert_eq!(lobby.get_current_turn_player(), Some(p1));
+    }
+
+    // ── Review ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_review_schedule_spaces_out_correct_answers() {
+        let missed = review::Schedule::default().review(false);
+        assert_eq!((missed.interval_days, missed.repetitions, missed.lapses), (1, 0, 1));
+        assert!(missed.ease_factor < 2.5);
+
+        let first = missed.review(true);
+        let second = first.review(true);
+        let third = second.review(true);
+        assert_eq!((first.interval_days, first.repetitions), (1, 1));
+        assert_eq!((second.interval_days, second.repetitions), (6, 2));
+        assert_eq!(third.interval_days, (6.0 * second.ease_factor).round() as u32);
+
+        // Missing again starts the prompt over, and ease never drops below the SM-2 floor
+        let relapsed = (0..10).fold(third, |s, _| s.review(false));
+        assert_eq!((relapsed.interval_days, relapsed.repetitions, relapsed.lapses), (1, 0, 11));
+        assert_eq!(relapsed.ease_factor, 1.3);
+    }
+
+    #[test]
+    fn test_review_key_ignores_answer_order() {
+        let reverse = |words: &[&str]| review::prompt_key(&ActivePrompt::ReverseVocab {
+            reading: "かみ".to_string(),
+            words: words.iter().map(|w| w.to_string()).collect(),
+        });
+        assert_eq!(reverse(&["紙", "髪", "神"]), reverse(&["神", "紙", "髪"]));
+        assert_eq!(reverse(&["紙"]), "ReverseVocab:かみ");
+
+        let compound = |characters: [&str; 2]| review::prompt_key(&ActivePrompt::Compound {
+            characters: characters.iter().map(|c| c.to_string()).collect(),
+        });
+        assert_eq!(compound(["日", "本"]), compound(["本", "日"]));
+
+        // The same characters asked in different modes are different items
+        let kanji = review::prompt_key(&ActivePrompt::Kanji { character: "日".to_string() });
+        let reading = review::prompt_key(&ActivePrompt::KanjiReading { character: "日".to_string(), readings: vec![] });
+        assert_ne!(kanji, reading);
+    }
+
+    #[test]
+    fn test_player_account_comes_from_the_socket() {
+        let lobby = create_test_lobby();
+        let alice = PlayerId::from("alice");
+        lobby.add_player(alice.clone(), "Alice".to_string()).unwrap();
+        let bot = lobby.add_bot(&alice, perfect_bot()).unwrap();
+        // Joining by name alone ties a player to no account
+        assert_eq!(lobby.get_player_account(&alice), None);
+
+        let account = uuid::Uuid::new_v4();
+        lobby.set_player_account(&alice, Some(account));
+        lobby.set_player_account(&bot, Some(account));
+        assert_eq!(lobby.get_player_account(&alice), Some(account));
+        assert_eq!(lobby.get_player_account(&bot), None);
+    }
+
+    #[test]
+    fn test_review_mode_serves_queued_prompts_first() {
+        let lobby = create_test_lobby();
+        let p1 = PlayerId::from("p1");
+        lobby.add_player(p1.clone(), "Alice".to_string()).unwrap();
+        lobby.settings.write(|s| s.mode = shared::GameMode::Review);
+        let queued = [
+            ActivePrompt::Vocab { word: "日本".to_string(), readings: vec!["にほん".to_string()] },
+            ActivePrompt::Kanji { character: "月".to_string() },
+        ];
+        lobby.prompt_queue.write(|q| q.extend(queued.iter().cloned()));
+
+        lobby.start_game(&p1).unwrap();
+        assert_eq!(lobby.current_prompt.read(|p| p.clone()), Some(queued[0].clone()));
+        lobby.process_guess(&p1, "にほん").unwrap();
+        assert_eq!(lobby.current_prompt.read(|p| p.clone()), Some(queued[1].clone()));
+
+        // Once the due prompts run out new ones are drawn as usual
+        lobby.process_guess(&p1, "月曜日").unwrap();
+        assert!(lobby.prompt_queue.read(|q| q.is_empty()));
+        assert!(lobby.current_prompt.read(|p| p.is_some()));
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Playing);
+    }
+
+    #[test]
+    fn test_other_modes_drop_leftover_review_prompts() {
+        let lobby = create_test_lobby();
+        let p1 = PlayerId::from("p1");
+        lobby.add_player(p1.clone(), "Alice".to_string()).unwrap();
+        lobby.settings.write(|s| s.mode = shared::GameMode::Zen);
+        lobby.prompt_queue.write(|q| q.push_back(ActivePrompt::Kanji { character: "月".to_string() }));
+
+        lobby.start_game(&p1).unwrap();
+        assert!(lobby.prompt_queue.read(|q| q.is_empty()));
+    }
+
+    // ── Romaji ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_romaji_to_kana() {
+        let cases = [
+            // Hepburn and Kunrei spellings of the same words
+            ("nihon", "にほん"),
+            ("shinbun", "しんぶん"),
+            ("sinbun", "しんぶん"),
+            ("shimbun", "しんぶん"),
+            ("chikatetsu", "ちかてつ"),
+            ("tikatetu", "ちかてつ"),
+            ("fuji", "ふじ"),
+            ("huzi", "ふじ"),
+            ("kyou", "きょう"),
+            ("sha", "しゃ"),
+            ("sya", "しゃ"),
+            ("tōkyō", "とうきょう"),
+            ("tôkyô", "とうきょう"),
+            ("okaasan", "おかあさん"),
+            ("wo", "を"),
+            // Small っ from doubled consonants
+            ("kko", "っこ"),
+            ("gakkou", "がっこう"),
+            ("kitte", "きって"),
+            ("zasshi", "ざっし"),
+            ("matcha", "まっちゃ"),
+            ("xtsu", "っ"),
+            // ん before consonants, at the end, doubled and with an apostrophe
+            ("hon", "ほん"),
+            ("kanji", "かんじ"),
+            ("onna", "おんな"),
+            ("konnichiha", "こんにちは"),
+            ("shinnyuu", "しんにゅう"),
+            ("nn", "ん"),
+            ("n'a", "んあ"),
+            ("kin'en", "きんえん"),
+            ("kinen", "きねん"),
+            ("hon'ya", "ほんや"),
+            ("honya", "ほにゃ"),
+            ("KANJI", "かんじ"),
+            ("ko-hi-", "こーひー"),
+        ];
+        for (romaji, kana) in cases {
+            assert_eq!(romaji::romaji_to_kana(romaji).as_deref(), Some(kana), "{}", romaji);
+        }
+
+        for not_romaji in ["", "にほん", "日本", "qqq", "nihon!", "x"] {
+            assert_eq!(romaji::romaji_to_kana(not_romaji), None, "{}", not_romaji);
+        }
+    }
+
+    #[test]
+    fn test_romaji_answers_follow_lobby_setting() {
+        let (lobby, leader) = setup_deathmatch_playing();
+        let set_prompt = || lobby.current_prompt.write(|p| *p = Some(ActivePrompt::Vocab {
+            word: "切手".to_string(),
+            readings: vec!["きって".to_string()],
+        }));
+
+        set_prompt();
+        lobby.process_guess(&leader, "kitte").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 0);
+
+        lobby.settings.write(|s| s.allow_romaji = true);
+        lobby.process_guess(&leader, "kitte").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+
+        // A trailing long-vowel dash is part of the romaji, not punctuation to trim
+        lobby.current_prompt.write(|p| *p = Some(ActivePrompt::Vocab {
+            word: "コーヒー".to_string(),
+            readings: vec!["コーヒー".to_string()],
+        }));
+        lobby.process_guess(&leader, "ko-hi-").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 2);
+
+        // Only Vocab takes romaji
+        lobby.current_prompt.write(|p| *p = Some(ActivePrompt::KanjiReading {
+            character: "月".to_string(),
+            readings: vec!["つき".to_string()],
+        }));
+        lobby.process_guess(&leader, "tsuki").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 2);
+    }
+
+    // ── Deinflection ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_dictionary_forms_undo_inflections() {
+        let cases = [
+            // Ichidan
+            ("食べた", "食べる"),
+            ("食べない", "食べる"),
+            ("食べなかった", "食べる"),
+            ("食べます", "食べる"),
+            ("食べました", "食べる"),
+            ("食べています", "食べる"),
+            ("食べられる", "食べる"),
+            ("食べたい", "食べる"),
+            ("食べれば", "食べる"),
+            // Godan
+            ("行きます", "行く"),
+            ("行った", "行く"),
+            ("行って", "行く"),
+            ("書いた", "書く"),
+            ("泳いで", "泳ぐ"),
+            ("話した", "話す"),
+            ("待って", "待つ"),
+            ("死んだ", "死ぬ"),
+            ("遊ばない", "遊ぶ"),
+            ("読みません", "読む"),
+            ("帰ろう", "帰る"),
+            ("買わなかった", "買う"),
+            ("書ける", "書く"),
+            ("書けない", "書く"),
+            ("書かせる", "書く"),
+            ("弄り回した", "弄り回す"),
+            // Adjectives
+            ("高くない", "高い"),
+            ("高かった", "高い"),
+            ("高くなかった", "高い"),
+            ("高くて", "高い"),
+            // する and 来る
+            ("勉強した", "勉強する"),
+            ("勉強しません", "勉強する"),
+            ("来た", "来る"),
+            ("来ない", "来る"),
+        ];
+        for (inflected, dictionary) in cases {
+            let forms = deinflect::dictionary_forms(inflected);
+            assert!(forms.iter().any(|f| f == dictionary), "{} -> {:?}", inflected, forms);
+        }
+
+        assert_eq!(deinflect::dictionary_forms("日本")[0], "日本");
+        // Endings alone aren't words
+        assert!(!deinflect::dictionary_forms("た").contains(&"る".to_string()));
+    }
+
+    #[test]
+    fn test_kanji_answers_accept_inflections_unless_exact() {
+        let (lobby, leader) = setup_deathmatch_playing();
+        let set_prompt = || lobby.current_prompt.write(|p| *p = Some(ActivePrompt::Kanji { character: "回".to_string() }));
+
+        set_prompt();
+        lobby.process_guess(&leader, "弄り回した").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+
+        lobby.settings.write(|s| s.exact_headwords = true);
+        set_prompt();
+        lobby.process_guess(&leader, "弄り回さない").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+        lobby.process_guess(&leader, "弄り回す").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_filtered_words_count_only_in_full_dictionary() {
+        let (mut lobby, leader) = setup_deathmatch_playing();
+        let mut dictionary = (*lobby.dict_list).clone();
+        let filter = data::WordFilter { denylist: HashSet::from(["月曜日".to_string()]), ..Default::default() };
+        assert_eq!(filter.apply(&mut dictionary), 1);
+        lobby.dict_list = Arc::new(dictionary);
+        let mut rx = lobby.tx.subscribe();
+        let set_prompt = || lobby.current_prompt.write(|p| *p = Some(ActivePrompt::Kanji { character: "日".to_string() }));
+
+        set_prompt();
+        lobby.process_guess(&leader, "月曜日").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 0);
+        // Filtered words aren't suggested either
+        let miss = loop {
+            let msg: shared::ServerMessage = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+            if let shared::ServerMessage::WordChecked { result, .. } = msg {
+                break result;
+            }
+        };
+        let suggestions = miss.suggestions.unwrap();
+        assert!(!suggestions.is_empty());
+        assert!(!suggestions.iter().any(|w| w.word == "月曜日"));
+
+        lobby.settings.write(|s| s.full_dictionary = true);
+        set_prompt();
+        lobby.process_guess(&leader, "月曜日").unwrap();
+        assert_eq!(lobby.get_player_score(&leader).unwrap(), 1);
+    }
+
+    // ── Shiritori ───────────────────────────────────────────────────────────
+
+    /// Two-player Shiritori game where p1 must continue from 日記 (にっき).
+    fn setup_shiritori_playing() -> (LobbyState, PlayerId, PlayerId) {
+        let lobby = create_test_lobby();
+        let p1 = PlayerId::from("p1");
+        let p2 = PlayerId::from("p2");
+        lobby.add_player(p1.clone(), "Alice".to_string()).unwrap();
+        lobby.add_player(p2.clone(), "Bob".to_string()).unwrap();
+        lobby.settings.write(|s| { s.mode = shared::GameMode::Shiritori; s.initial_lives = Some(3); });
+        lobby.players.write(|ps| ps.iter_mut().for_each(|p| p.lives = Some(3)));
+        lobby.game_status.write(|s| *s = GameStatus::Playing);
+        lobby.turn_order.write(|o| { o.push(p1.clone()); o.push(p2.clone()); });
+        lobby.active_level_indices.write(|i| i.push(0));
+        lobby.used_words.write(|w| { w.insert("日記".to_string()); });
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Shiritori {
+            word: "日記".to_string(),
+            reading: "にっき".to_string(),
+        }));
+        (lobby, p1, p2)
+    }
+
+    #[test]
+    fn test_shiritori_tail_rules() {
+        assert_eq!(utils::shiritori_tail("にっき"), Some('き'));
+        assert_eq!(utils::shiritori_tail("コーヒー"), Some('ひ'));
+        assert_eq!(utils::shiritori_tail("きしゃ"), Some('や'));
+        assert!(utils::ends_with_n("にほん"));
+    }
+
+    #[test]
+    fn test_shiritori_valid_word_becomes_prompt() {
+        let (lobby, p1, p2) = setup_shiritori_playing();
+        lobby.process_guess(&p1, "切手").unwrap();
+
+        assert_eq!(lobby.get_player_score(&p1).unwrap(), 1);
+        assert_eq!(lobby.get_current_prompt_text(), Some("切手（きって）".to_string()));
+        assert_eq!(lobby.get_current_turn_player(), Some(p2.clone()));
+
+        // Next player chains off て
+        lobby.process_guess(&p2, "手紙").unwrap();
+        assert_eq!(lobby.get_player_score(&p2).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_shiritori_wrong_kana_costs_life_and_keeps_chain() {
+        let (lobby, p1, p2) = setup_shiritori_playing();
+        lobby.process_guess(&p1, "理由").unwrap();
+
+        assert_eq!(lobby.get_player_score(&p1).unwrap(), 0);
+        let lives = lobby.players.read(|ps| ps.iter().find(|p| p.id == p1).and_then(|p| p.lives));
+        assert_eq!(lives, Some(2));
+        assert_eq!(lobby.get_current_prompt_text(), Some("日記（にっき）".to_string()));
+        assert_eq!(lobby.get_current_turn_player(), Some(p2));
+    }
+
+    #[test]
+    fn test_shiritori_used_word_rejected() {
+        let (lobby, p1, _p2) = setup_shiritori_playing();
+        lobby.used_words.write(|w| { w.insert("切手".to_string()); });
+        lobby.process_guess(&p1, "切手").unwrap();
+        assert_eq!(lobby.get_player_score(&p1).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_shiritori_word_ending_in_n_eliminates() {
+        let (lobby, p1, _p2) = setup_shiritori_playing();
+        // Chain off に so that 日本 (にほん) is a legal but fatal answer
+        lobby.current_prompt.write(|k| *k = Some(ActivePrompt::Shiritori {
+            word: "蟹".to_string(),
+            reading: "かに".to_string(),
+        }));
+        lobby.process_guess(&p1, "日本").unwrap();
+
+        let eliminated = lobby.players.read(|ps| ps.iter().find(|p| p.id == p1).map(|p| p.is_eliminated));
+        assert_eq!(eliminated, Some(true));
+        assert!(!lobby.turn_order.read(|o| o.contains(&p1)));
+        assert_eq!(lobby.game_status.read(|s| *s), GameStatus::Finished);
+    }
+
     #[tokio::test]
     async fn test_get_public_lobbies() {
         let app_state = AppState::create().expect("Failed to create AppState");
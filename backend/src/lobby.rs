@@ -1,30 +1,47 @@
-use chrono::Utc;
-use rand::{RngExt, distr::{Distribution, weighted::WeightedIndex}};
+use chrono::{NaiveDate, Utc};
+use rand::{RngExt, SeedableRng, distr::{Distribution, weighted::WeightedIndex}, rngs::StdRng};
 use tokio::sync::broadcast;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
     sync::Arc,
 };
 
 pub use shared::{
     CheckWordResponse, GameSettings, GameStatus, JoinLobbyRequest, PlayerId, ApiContext,
-    ContentMode, ActivePrompt, LobbyId, LobbyInfo
+    ContentMode, ActivePrompt, DifficultySet, LobbyId, LobbyInfo
 };
 pub use crate::{
-    utils::check_prompt,
+    utils::{check_prompt, clean_answer, daily_challenge_seed, shiritori_reading, shiritori_tail, ends_with_n, is_kana, to_hiragana},
     types::{Result, Shared, PlayerData},
-    data::{JlptWordData, KanjiData, DictData},
+    data::{GameData, GlossData, JlptWordData, Kanji, KanjiData, DictData, DictEntry, ReadingData, SpellingData, WordRankData},
     error::AppError,
 };
 
 
 
 
+/// Pause on the game-over screen before the next game of a series starts.
+const SERIES_NEXT_GAME_DELAY_SECS: u64 = 8;
+
+/// Rank assumed for vocab words with no known frequency, so they still come up now and then.
+const UNRANKED_WORD_RANK: u32 = 50_000;
+
+/// How often a vocab word with frequency `rank` is picked in weighted games.
+fn vocab_weight(rank: Option<u32>) -> f64 {
+    1.0 / (rank.unwrap_or(UNRANKED_WORD_RANK).max(1) as f64).sqrt()
+}
+
 #[derive(Clone)]
 pub struct LobbyState {
     pub kanji_list: Arc<KanjiData>,
+    /// Kanji left out of prompts for having too few words, still looked up for their details
+    pub hard_kanji_list: Arc<KanjiData>,
     pub word_list: Arc<JlptWordData>,
     pub dict_list: Arc<DictData>,
+    pub reading_list: Arc<ReadingData>,
+    pub spelling_list: Arc<SpellingData>,
+    pub gloss_list: Arc<GlossData>,
+    pub word_ranks: Arc<WordRankData>,
     pub players: Shared<Vec<PlayerData>>,
     pub lobby_leader: Shared<PlayerId>,
     pub settings: Shared<GameSettings>,
@@ -33,6 +50,8 @@ pub struct LobbyState {
     pub tx: broadcast::Sender<String>,
     pub active_level_indices: Shared<Vec<usize>>,
     pub level_weights: Shared<HashMap<usize, WeightedIndex<f64>>>,
+    /// Per-level weights over `word_list[level].keys()`, in that map's iteration order
+    pub vocab_weights: Shared<HashMap<usize, WeightedIndex<f64>>>,
     pub game_session_id: Option<uuid::Uuid>,
     pub turn_order: Shared<Vec<PlayerId>>,
     pub current_turn_index: Shared<usize>,
@@ -43,15 +62,34 @@ pub struct LobbyState {
     pub reuse_prompt: Shared<bool>,
     pub cleanup_generation: Shared<u64>,
     pub timer_expires_at: Shared<Option<u64>>,
+    pub used_words: Shared<HashSet<String>>,
+    pub survival_streak: Shared<u32>,
+    pub daily_date: Shared<Option<NaiveDate>>,
+    /// Seeded prompt source while a daily challenge is running
+    pub daily_rng: Shared<Option<StdRng>>,
+    /// Shared between a tournament lobby and its match lobbies
+    pub bracket: Shared<Option<shared::Bracket>>,
+    /// Bumped each time a Bomb fuse is lit so stale fuses fizzle out
+    pub fuse_generation: Shared<u64>,
+    /// Kept across games until someone clinches the series or the leader changes its rules
+    pub series: Shared<Option<shared::SeriesStandings>>,
+    /// Prompts served before any random ones, such as a player's due reviews
+    pub prompt_queue: Shared<VecDeque<ActivePrompt>>,
+    /// Players whose miss on the current prompt has already gone into their review queue
+    pub review_misses: Shared<HashSet<PlayerId>>,
 }
 
 impl LobbyState {
-        pub fn new(kanji_list: Arc<KanjiData>, word_list: Arc<JlptWordData>,
-        dict_list: Arc<DictData>, game_session_id: Option<uuid::Uuid>, db_pool: Option<Arc<crate::db::DbPool>>) -> Self {
+        pub fn new(data: &GameData, game_session_id: Option<uuid::Uuid>, db_pool: Option<Arc<crate::db::DbPool>>) -> Self {
         Self {
-            kanji_list,
-            word_list,
-            dict_list,
+            kanji_list: Arc::clone(&data.kanji),
+            hard_kanji_list: Arc::clone(&data.hard_kanji),
+            word_list: Arc::clone(&data.words),
+            dict_list: Arc::clone(&data.dictionary),
+            reading_list: Arc::clone(&data.readings),
+            spelling_list: Arc::clone(&data.spellings),
+            gloss_list: Arc::clone(&data.glosses),
+            word_ranks: Arc::clone(&data.word_ranks),
             players: Shared::new(Vec::new()),
             lobby_leader: Shared::new(PlayerId::default()),
             settings: Shared::new(GameSettings::default()),
@@ -60,6 +98,7 @@ impl LobbyState {
             tx: broadcast::channel(100).0, // .0 = Sender, .1 = Receiver
             active_level_indices: Shared::new(Vec::new()),
             level_weights: Shared::new(HashMap::new()),
+            vocab_weights: Shared::new(HashMap::new()),
             game_session_id,
             turn_order: Shared::new(Vec::new()),
             current_turn_index: Shared::new(0),
@@ -70,6 +109,15 @@ impl LobbyState {
             reuse_prompt: Shared::new(false),
             cleanup_generation: Shared::new(0),
             timer_expires_at: Shared::new(None),
+            used_words: Shared::new(HashSet::new()),
+            survival_streak: Shared::new(0),
+            daily_date: Shared::new(None),
+            daily_rng: Shared::new(None),
+            bracket: Shared::new(None),
+            fuse_generation: Shared::new(0),
+            series: Shared::new(None),
+            prompt_queue: Shared::new(VecDeque::new()),
+            review_misses: Shared::new(HashSet::new()),
         }
     }
 
@@ -86,17 +134,62 @@ impl LobbyState {
         })
     }
 
+    /// Solo modes need exactly one person playing and no bots, so runs and reviews belong to a single account.
+    pub fn check_solo_players(&self, mode: shared::GameMode) -> Result<()> {
+        if !mode.is_solo() {
+            return Ok(());
+        }
+        let (humans, bots) = self.players.read(|players| (
+            players.iter().filter(|p| p.bot.is_none() && !p.is_spectator).count(),
+            players.iter().filter(|p| p.bot.is_some()).count(),
+        ));
+        if bots > 0 {
+            return Err(AppError::InvalidInput(format!("{:?} can't be played with bots", mode)));
+        }
+        if humans != 1 {
+            return Err(AppError::InvalidInput(format!("{:?} needs exactly one player, not {}", mode, humans)));
+        }
+        Ok(())
+    }
+
     pub fn update_settings(&self, player_id: &PlayerId, new_settings: GameSettings) -> Result<()> {
         if !self.is_leader(player_id) {
             return Err(AppError::AuthError(
                 "Only lobby leader can change settings".to_string(),
             ));
         }
+        if !self.available_content_modes().contains(&new_settings.content_mode) {
+            return Err(AppError::InvalidInput(format!("{:?} needs data this server hasn't loaded", new_settings.content_mode)));
+        }
 
+        let mut team_names = HashSet::new();
+        let mut assigned = HashSet::new();
+        for team in &new_settings.teams {
+            if team.name.trim().is_empty() {
+                return Err(AppError::InvalidInput("Team name cannot be empty".to_string()));
+            }
+            if !team_names.insert(team.name.trim()) {
+                return Err(AppError::InvalidInput(format!("Duplicate team name: {}", team.name)));
+            }
+            if team.members.iter().any(|m| !assigned.insert(m)) {
+                return Err(AppError::InvalidInput("A player can only be on one team".to_string()));
+            }
+        }
+        // Only players in the lobby can be on a team, so no stale id is carried into the standings
+        let unknown = self.players.read(|players| assigned.iter().any(|&m| !players.iter().any(|p| &p.id == m)));
+        if unknown {
+            return Err(AppError::InvalidInput("Teams can only include players in the lobby".to_string()));
+        }
+
+        let series_changed = self.settings.read(|s| s.mode != new_settings.mode || s.series_length != new_settings.series_length);
         self.settings.write(|settings| {
             *settings = new_settings.clone();
         });
 
+        if series_changed && self.series.write(|s| s.take()).is_some() {
+            self.broadcast(shared::ServerMessage::SeriesUpdate { series: None });
+        }
+
         self.broadcast(shared::ServerMessage::SettingsUpdate {
             settings: new_settings
         });
@@ -124,7 +217,9 @@ impl LobbyState {
                 is_eliminated: p.is_eliminated,
                 is_connected: p.is_connected,
                 is_spectator: p.is_spectator,
-                is_turn: current_turn.as_ref() == Some(&p.id) && status == GameStatus::Playing && settings.mode == shared::GameMode::Duel,
+                is_turn: current_turn.as_ref() == Some(&p.id) && status == GameStatus::Playing && settings.mode.is_turn_based(),
+                team: settings.team_of(&p.id).map(|t| t.name.clone()),
+                is_bot: p.bot.is_some(),
             })
             .collect::<Vec<_>>()
         });
@@ -135,9 +230,27 @@ impl LobbyState {
             players: api_players,
             settings,
             status,
+            tier: self.get_current_tier(),
+            bracket: self.bracket.read(|b| b.clone()),
+            series: self.series.read(|s| s.clone()),
+            content_modes: self.available_content_modes(),
         }
     }
 
+    /// Content modes the loaded data can play: Meaning needs English glosses from JMdict,
+    /// and KanjiReading needs readings from KANJIDIC.
+    pub fn available_content_modes(&self) -> Vec<ContentMode> {
+        let has_readings = self.kanji_list.iter().flatten()
+            .any(|k| !k.readings.on.is_empty() || !k.readings.kun.is_empty());
+        ContentMode::ALL.into_iter()
+            .filter(|mode| match mode {
+                ContentMode::Meaning => !self.gloss_list.is_empty(),
+                ContentMode::KanjiReading => has_readings,
+                _ => true,
+            })
+            .collect()
+    }
+
     pub fn start_game(&self, player_id: &PlayerId) -> Result<()> {
         if !self.is_leader(player_id) {
             return Err(AppError::AuthError(
@@ -150,30 +263,65 @@ impl LobbyState {
             return Err(AppError::InvalidInput("game is not in lobby state".to_string()))?;
         }
 
-        let settings = self.settings.read(|s| s.clone());
+        let mut settings = self.settings.read(|s| s.clone());
+        self.check_solo_players(settings.mode)?;
+        if settings.mode == shared::GameMode::Daily {
+            settings = settings.daily_challenge();
+            self.settings.write(|s| *s = settings.clone());
+            self.broadcast(shared::ServerMessage::SettingsUpdate { settings: settings.clone() });
+        }
+        if settings.is_team_game() {
+            settings = self.assign_unteamed_players();
+        }
 
         {
             let levels = &settings.difficulty_levels;
             let weighted = settings.weighted;
+            // Word-based prompts can only come from sets with JLPT vocabulary
+            let needs_words = settings.content_mode.uses_words() || settings.mode == shared::GameMode::Shiritori;
 
             let mut indices: Vec<usize> = Vec::new();
-            for level in levels {
-                let idx = match level.as_str() {
-                    "N1" => 0, "N2" => 1, "N3" => 2, "N4" => 3, "N5" => 4, _ => 99
-                };
-
-                if idx < self.kanji_list.len()  && !self.kanji_list[idx].is_empty() {
+            // Survival ignores the chosen levels, so they don't need to be playable
+            let chosen = levels.iter().filter(|_| settings.mode != shared::GameMode::Survival);
+            for level in chosen.filter(|l| !needs_words || l.has_words()) {
+                let Some(idx) = level.index() else { continue };
+
+                // Rather than quietly play a different set, say which one can't be played
+                if self.kanji_list.get(idx).is_none_or(Vec::is_empty) {
+                    return Err(AppError::InvalidInput(format!("{} has no kanji to play", level.label())))?;
+                }
+                if !indices.contains(&idx) {
                     indices.push(idx);
                 }
             }
 
-            if indices.is_empty() && self.kanji_list.len() > 4 {
+            if indices.is_empty() && needs_words && levels.iter().any(|l| !l.has_words()) {
+                return Err(AppError::InvalidInput("Word-based modes need at least one JLPT level".to_string()))?;
+            }
+            if indices.is_empty() && self.kanji_list.get(4).is_some_and(|l| !l.is_empty()) {
                 indices.push(4);
             }
 
+            // Survival ignores the chosen levels and climbs from the bottom of the ladder
+            let indices: Vec<usize> = if settings.mode == shared::GameMode::Survival {
+                self.survival_ladder().into_iter().take(1).collect()
+            } else {
+                indices
+            };
+            // Levels can be emptied at startup when all their kanji went to the hard pool
+            if indices.is_empty() {
+                return Err(AppError::InvalidInput("None of the selected levels have any kanji to play".to_string()))?;
+            }
+
             let mut w_map = HashMap::new();
+            let mut vocab_map = HashMap::new();
             if weighted {
-                for &idx in &indices {
+                let weighted_levels: Vec<usize> = if settings.mode == shared::GameMode::Survival {
+                    self.survival_ladder()
+                } else {
+                    indices.clone()
+                };
+                for idx in weighted_levels {
                     let list = &self.kanji_list[idx];
                     let weights: Vec<f64> = list.iter()
                         .map(|k| if k.frequency > 0 { k.frequency as f64 } else { 0.0 })
@@ -182,11 +330,29 @@ impl LobbyState {
                     if let Ok(dist) = WeightedIndex::new(&weights) {
                         w_map.insert(idx, dist);
                     }
+
+                    if let Some(word_map) = self.word_list.get(idx) {
+                        let weights: Vec<f64> = word_map.keys()
+                            .map(|w| vocab_weight(self.word_ranks.get(w).copied()))
+                            .collect();
+                        if let Ok(dist) = WeightedIndex::new(&weights) {
+                            vocab_map.insert(idx, dist);
+                        }
+                    }
                 }
             }
 
             self.active_level_indices.write(|ai| *ai = indices);
             self.level_weights.write(|lw| *lw = w_map);
+            self.vocab_weights.write(|vw| *vw = vocab_map);
+            self.survival_streak.write(|s| *s = 0);
+
+            let daily_date = (settings.mode == shared::GameMode::Daily).then(|| Utc::now().date_naive());
+            self.daily_date.write(|d| *d = daily_date);
+            self.daily_rng.write(|r| *r = daily_date.map(|d| StdRng::seed_from_u64(daily_challenge_seed(d))));
+            if settings.mode != shared::GameMode::Review {
+                self.prompt_queue.write(|q| q.clear());
+            }
 
             self.players.write(|players| {
                 self.turn_order.write(|turn_order| {
@@ -198,14 +364,14 @@ impl LobbyState {
                             p.score = 0;
                             p.is_eliminated = false;
                             p.is_spectator = false;
-                            if settings.mode == shared::GameMode::Duel {
+                            if settings.mode.is_turn_based() {
                                 p.lives = settings.initial_lives;
                                 turn_order.push(p.id.clone());
                             } else {
                                 p.lives = None;
                             }
                         }
-                        if settings.mode == shared::GameMode::Duel {
+                        if settings.mode.is_turn_based() {
                             use rand::seq::SliceRandom;
                             let mut rng = rand::rng();
                             turn_order.shuffle(&mut rng);
@@ -213,25 +379,83 @@ impl LobbyState {
                      })
                 })
             });
+            self.used_words.write(|w| w.clear());
         }
 
+        // Carry on the current series, or begin a fresh one if the last was won
+        let series = self.series.write(|series| {
+            let best_of = settings.series_length.unwrap_or(1);
+            if !settings.is_series() {
+                *series = None;
+            } else if series.as_ref().is_none_or(|s| s.champion.is_some() || s.best_of != best_of) {
+                *series = Some(shared::SeriesStandings::new(best_of));
+            }
+            series.clone()
+        });
+        self.broadcast(shared::ServerMessage::SeriesUpdate { series });
+
         self.generate_random_prompt(false, true)?;
 
         self.game_status.write(|status| *status = GameStatus::Playing);
 
+        if settings.mode.is_timed_run() {
+            self.start_time_attack_clock(settings.timer_seconds().unwrap_or(shared::DEFAULT_TIME_ATTACK_SECONDS));
+        } else if settings.mode == shared::GameMode::Bomb {
+            self.light_fuse();
+        }
+
         self.broadcast(shared::ServerMessage::GameState {
             prompt: self.get_current_prompt_text().unwrap_or_default(),
             status: GameStatus::Playing,
             scores: self.get_all_players(),
             timer_expires_at: self.timer_expires_at.read(|t| *t),
+            tier: self.get_current_tier(),
+            kanji: self.get_current_prompt_kanji(),
         });
 
         Ok(())
     }
 
     pub fn add_player(&self, player_id: PlayerId, player_name: String) -> Result<bool> {
+        self.insert_player(player_id, player_name, None)
+    }
+
+    /// Leader adds a computer-controlled player before the game starts.
+    /// Only the player entry is created here; `crate::bot::spawn_bot` makes it play.
+    pub fn add_bot(&self, requestor_id: &PlayerId, profile: shared::BotProfile) -> Result<PlayerId> {
+        if !self.is_leader(requestor_id) {
+            return Err(AppError::AuthError("Only the lobby leader can add bots".to_string()));
+        }
+        if self.game_status.read(|s| *s) != GameStatus::Lobby {
+            return Err(AppError::InvalidInput("Bots can only be added before the game starts".to_string()));
+        }
+        let mode = self.settings.read(|s| s.mode);
+        if mode.is_solo() {
+            return Err(AppError::InvalidInput(format!("{:?} can't be played with bots", mode)));
+        }
+        if profile.min_delay_ms > profile.max_delay_ms || profile.accuracy > 100 || profile.common_bias > 100 {
+            return Err(AppError::InvalidInput("Invalid bot profile".to_string()));
+        }
+
+        let max_players = self.settings.read(|s| s.max_players) as usize;
+        let name = self.players.read(|players| {
+            if players.len() >= max_players {
+                return Err(AppError::InvalidInput("Lobby is full".to_string()));
+            }
+            Ok((1..).map(|n| format!("Bot {}", n)).find(|name| !players.iter().any(|p| &p.name == name)).unwrap_or_default())
+        })?;
+
+        let player_id = PlayerId::from(format!("bot-{}", crate::utils::generate_player_id()));
+        self.insert_player(player_id.clone(), name, Some(profile))?;
+        Ok(player_id)
+    }
+
+    /// Adds a human, or a bot when `bot` is set, to the player list.
+    /// Returns whether they became the lobby leader.
+    pub(crate) fn insert_player(&self, player_id: PlayerId, player_name: String, bot: Option<shared::BotProfile>) -> Result<bool> {
         let is_leader_result = self.players.write(|players| {
-            let is_leader = players.is_empty();
+            // Bots can't run the lobby, so the first human in leads it
+            let is_leader = bot.is_none() && !players.iter().any(|p| p.bot.is_none());
             if is_leader {
                  self.lobby_leader.write(|leader| *leader = player_id.clone());
             }
@@ -258,6 +482,8 @@ impl LobbyState {
                 is_eliminated: false,
                 is_connected: true,
                 is_spectator,
+                bot,
+                user_id: None,
             });
             Ok(is_leader)
         })?;
@@ -288,12 +514,14 @@ impl LobbyState {
             return Err(AppError::AuthError("Only the lobby leader can promote a new leader".to_string()));
         }
 
-        let player_exists = self.players.read(|players| {
-            players.iter().any(|p| &p.id == target_player_id)
+        let target = self.players.read(|players| {
+            players.iter().find(|p| &p.id == target_player_id).map(|p| p.bot.is_some())
         });
 
-        if !player_exists {
-            return Err(AppError::InvalidInput("Target player is not in the lobby".to_string()));
+        match target {
+            None => return Err(AppError::InvalidInput("Target player is not in the lobby".to_string())),
+            Some(true) => return Err(AppError::InvalidInput("Bots can't lead the lobby".to_string())),
+            Some(false) => {},
         }
 
         self.lobby_leader.write(|leader| {
@@ -307,7 +535,8 @@ impl LobbyState {
     }
 
     pub fn remove_player(&self, player_id: &PlayerId) -> bool {
-        self.players.write(|players| {
+        let settings = self.settings.read(|s| s.clone());
+        let removed = self.players.write(|players| {
             if let Some(pos) = players.iter().position(|p| &p.id == player_id) {
                 let p_id = players[pos].id.clone();
                 players.remove(pos);
@@ -325,7 +554,7 @@ impl LobbyState {
 
                 self.lobby_leader.write(|leader| {
                     if leader.to_string() == player_id.to_string() {
-                        if let Some(new_leader) = players.first() {
+                        if let Some(new_leader) = players.iter().find(|p| p.bot.is_none()) {
                             tracing::info!("Reassigned lobby leader to {}", new_leader.id.0);
                             *leader = new_leader.id.clone();
                             self.broadcast(shared::ServerMessage::LeaderUpdate {
@@ -349,6 +578,8 @@ impl LobbyState {
                         is_connected: p.is_connected,
                         is_spectator: p.is_spectator,
                         is_turn: false,
+                        team: settings.team_of(&p.id).map(|t| t.name.clone()),
+                        is_bot: p.bot.is_some(),
                     }).collect()
                 };
 
@@ -359,7 +590,49 @@ impl LobbyState {
                 tracing::info!("remove_player: player {} already removed or not found (likely already cleaned up)", player_id.0);
                 false
             }
-        })
+        });
+
+        if removed {
+            self.remove_from_teams(player_id);
+        }
+        removed
+    }
+
+    /// Puts everyone still without a team on the smallest one, so nobody plays a team game outside the standings.
+    fn assign_unteamed_players(&self) -> GameSettings {
+        let playing: Vec<PlayerId> = self.players.read(|players| players.iter().map(|p| p.id.clone()).collect());
+        let (settings, changed) = self.settings.write(|settings| {
+            let mut changed = false;
+            for id in playing {
+                if settings.team_of(&id).is_some() {
+                    continue;
+                }
+                if let Some(team) = settings.teams.iter_mut().min_by_key(|t| t.members.len()) {
+                    team.members.push(id);
+                    changed = true;
+                }
+            }
+            (settings.clone(), changed)
+        });
+        if changed {
+            self.broadcast(shared::ServerMessage::SettingsUpdate { settings: settings.clone() });
+        }
+        settings
+    }
+
+    /// Takes a departed player off their team, so the id isn't still on it if they rejoin.
+    fn remove_from_teams(&self, player_id: &PlayerId) {
+        let settings = self.settings.write(|settings| {
+            let before = settings.teams.iter().map(|t| t.members.len()).sum::<usize>();
+            for team in &mut settings.teams {
+                team.members.retain(|m| m != player_id);
+            }
+            let after = settings.teams.iter().map(|t| t.members.len()).sum::<usize>();
+            (after != before).then(|| settings.clone())
+        });
+        if let Some(settings) = settings {
+            self.broadcast(shared::ServerMessage::SettingsUpdate { settings });
+        }
     }
 
     pub fn set_player_connected(&self, player_id: &PlayerId, is_connected: bool) -> bool {
@@ -386,12 +659,19 @@ impl LobbyState {
         changed
     }
 
+    /// Whether every human has dropped off; bots never disconnect so they don't count.
     pub fn all_disconnected(&self) -> bool {
         self.players.read(|players| {
-            !players.is_empty() && players.iter().all(|p| !p.is_connected)
+            let mut humans = players.iter().filter(|p| p.bot.is_none()).peekable();
+            humans.peek().is_some() && humans.all(|p| !p.is_connected)
         })
     }
 
+    /// Whether only bots (or nobody) are left, in which case the lobby can be closed.
+    pub fn has_no_humans(&self) -> bool {
+        self.players.read(|players| players.iter().all(|p| p.bot.is_some()))
+    }
+
     pub fn get_player_score(&self, player_id: &PlayerId) -> Result<u32> {
         self.players.read(|players| {
             players
@@ -402,6 +682,20 @@ impl LobbyState {
         })
     }
 
+    /// Ties the player to the account their socket authenticated as.
+    pub fn set_player_account(&self, player_id: &PlayerId, user_id: Option<uuid::Uuid>) {
+        self.players.write(|players| {
+            if let Some(p) = players.iter_mut().find(|p| &p.id == player_id && p.bot.is_none()) {
+                p.user_id = user_id;
+            }
+        });
+    }
+
+    /// The account the player's socket authenticated as, if any.
+    pub fn get_player_account(&self, player_id: &PlayerId) -> Option<uuid::Uuid> {
+        self.players.read(|players| players.iter().find(|p| &p.id == player_id).and_then(|p| p.user_id))
+    }
+
     pub fn get_player_name(&self, player_id: &PlayerId) -> Result<String> {
         self.players.read(|players| {
             players
@@ -424,6 +718,17 @@ impl LobbyState {
         })
     }
 
+    /// Combined score of every player on `team_name`.
+    pub fn get_team_score(&self, team_name: &str) -> u32 {
+        let settings = self.settings.read(|s| s.clone());
+        self.players.read(|players| {
+            players.iter()
+                .filter(|p| settings.team_of(&p.id).is_some_and(|t| t.name == team_name))
+                .map(|p| p.score)
+                .sum()
+        })
+    }
+
     pub fn get_all_players(&self) -> Vec<shared::PlayerData> {
         let status = self.game_status.read(|s| *s);
         let settings = self.settings.read(|s| s.clone());
@@ -439,9 +744,11 @@ impl LobbyState {
                 is_connected: p.is_connected,
                 is_spectator: p.is_spectator,
                 is_turn: false, // Default to false here
+                team: settings.team_of(&p.id).map(|t| t.name.clone()),
+                is_bot: p.bot.is_some(),
             }).collect();
 
-            if settings.mode == shared::GameMode::Duel && status == GameStatus::Playing {
+            if settings.mode.is_turn_based() && status == GameStatus::Playing {
                 self.turn_order.read(|order| {
                     self.current_turn_index.read(|idx| {
                          if let Some(current_id) = order.get(*idx) {
@@ -458,16 +765,100 @@ impl LobbyState {
         })
     }
 
+    /// Level indices Survival climbs through, easiest first, skipping levels with no data.
+    fn survival_ladder(&self) -> Vec<usize> {
+        [4, 3, 2, 1, 0].into_iter()
+            .filter(|&idx| idx < self.kanji_list.len() && !self.kanji_list[idx].is_empty())
+            .collect()
+    }
+
+    /// The JLPT level Survival is currently drawing prompts from.
+    pub fn get_current_tier(&self) -> Option<String> {
+        if self.settings.read(|s| s.mode) != shared::GameMode::Survival {
+            return None;
+        }
+        self.active_level_indices.read(|i| i.first().and_then(|&idx| DifficultySet::ALL.get(idx)).map(|s| s.label()))
+    }
+
+    /// Counts a correct Survival answer and moves up a level once the streak is long enough.
+    /// Returns the new tier when the player climbed.
+    fn climb_survival_ladder(&self) -> Option<String> {
+        let needed = self.settings.read(|s| s.survival_streak.unwrap_or(5)).max(1);
+        let streak = self.survival_streak.write(|s| { *s += 1; *s });
+        if streak < needed {
+            return None;
+        }
+
+        let ladder = self.survival_ladder();
+        let current = self.active_level_indices.read(|i| i.first().copied())?;
+        let pos = ladder.iter().position(|&idx| idx == current)?;
+        let next = *ladder.get(pos + 1)?;
+
+        self.survival_streak.write(|s| *s = 0);
+        self.active_level_indices.write(|i| *i = vec![next]);
+        self.get_current_tier()
+    }
+
+    /// Whether the game is over once `remaining` players are left in the turn order.
+    /// Survival keeps going for as long as anyone is alive.
+    fn is_last_standing(&self, remaining: usize) -> bool {
+        if self.settings.read(|s| s.mode) == shared::GameMode::Survival {
+            remaining == 0
+        } else {
+            remaining <= 1
+        }
+    }
+
     pub fn get_current_prompt_text(&self) -> Option<String> {
-        self.current_prompt.read(|p| p.as_ref().map(|prompt| prompt.display_text().to_string()))
+        self.current_prompt.read(|p| p.as_ref().map(|prompt| prompt.display_text()))
+    }
+
+    /// KANJIDIC2 details for the kanji of the current prompt.
+    pub fn get_current_prompt_kanji(&self) -> Vec<shared::KanjiInfo> {
+        self.current_prompt.read(|p| p.as_ref().map(|prompt| self.prompt_kanji(prompt, false)).unwrap_or_default())
+    }
+
+    /// Kanji details to send with a `CheckWordResponse` that may carry a new prompt.
+    fn new_prompt_kanji(&self, new_prompt: &Option<String>) -> Vec<shared::KanjiInfo> {
+        if new_prompt.is_some() { self.get_current_prompt_kanji() } else { Vec::new() }
+    }
+
+    /// KANJIDIC2 details for the kanji in `prompt`, leaving out kanji it has nothing on.
+    /// Reading prompts only get them once `missed`, since the readings are the answer.
+    fn prompt_kanji(&self, prompt: &ActivePrompt, missed: bool) -> Vec<shared::KanjiInfo> {
+        let characters: Vec<&String> = match prompt {
+            ActivePrompt::Kanji { character } => vec![character],
+            ActivePrompt::Compound { characters } => characters.iter().collect(),
+            ActivePrompt::KanjiReading { character, .. } if missed => vec![character],
+            _ => return Vec::new(),
+        };
+        characters.into_iter()
+            .filter_map(|c| self.kanji_list.iter().chain(self.hard_kanji_list.iter()).flatten().find(|k| k.kanji == *c))
+            .map(Kanji::info)
+            .filter(shared::KanjiInfo::has_details)
+            .collect()
     }
 
     /// Generate a new random kanji and store it as current.
     /// If `broadcast` is true, a `PromptUpdate` WS message is sent to all clients.
     /// Pass `false` when the caller will send a more complete message (e.g. `GameState`).
     pub fn generate_random_prompt(&self, broadcast: bool, reset_timer: bool) -> Result<String> {
-        let content_mode = self.settings.read(|s| s.content_mode.clone());
-        let mut rng = rand::rng();
+        if let Some(prompt) = self.prompt_queue.write(|q| q.pop_front()) {
+            return Ok(self.set_prompt(prompt, broadcast, reset_timer));
+        }
+
+        // A daily challenge walks its seeded sequence so every run that day sees the same prompts
+        let prompt = match self.daily_rng.write(|rng| rng.as_mut().map(|rng| self.draw_prompt(rng))) {
+            Some(prompt) => prompt?,
+            None => self.draw_prompt(&mut rand::rng())?,
+        };
+
+        Ok(self.set_prompt(prompt, broadcast, reset_timer))
+    }
+
+    /// Pick the next prompt for the current mode and content type using `rng`.
+    fn draw_prompt(&self, rng: &mut impl rand::Rng) -> Result<ActivePrompt> {
+        let (mode, content_mode) = self.settings.read(|s| (s.mode, s.content_mode.clone()));
         let indices = self.active_level_indices.read(|i| i.clone());
 
         if indices.is_empty() {
@@ -476,49 +867,187 @@ impl LobbyState {
 
         let level_idx = indices[rng.random_range(0..indices.len())];
 
-        let display_text = match content_mode {
-            ContentMode::Kanji => {
-                let weights_map = self.level_weights.read(|w| w.clone());
-                let kanji_list = &self.kanji_list[level_idx];
+        let prompt = if mode == shared::GameMode::Shiritori {
+            // The chain needs a starting word that doesn't immediately end the game
+            let word_map = &self.word_list[level_idx];
+            let candidates = word_map.iter()
+                .filter_map(|(word, readings)| {
+                    readings.iter()
+                        .find(|r| r.chars().all(is_kana) && !ends_with_n(r))
+                        .map(|r| (word, r))
+                })
+                .collect::<Vec<_>>();
+            if candidates.is_empty() {
+                return Err(AppError::InternalError("No shiritori starting words for level".into()));
+            }
+            let (word, reading) = candidates[rng.random_range(0..candidates.len())];
+            self.used_words.write(|w| { w.insert(word.clone()); });
 
-                let kanji = if let Some(dist) = weights_map.get(&level_idx) {
-                    kanji_list[dist.sample(&mut rng)].clone()
-                } else {
-                    kanji_list[rng.random_range(0..kanji_list.len())].clone()
-                };
+            ActivePrompt::Shiritori { word: word.clone(), reading: reading.clone() }
+        } else {
+            match content_mode {
+                ContentMode::Kanji => {
+                    let kanji = self.pick_kanji(level_idx, rng)
+                        .ok_or_else(|| AppError::InvalidInput("No kanji are loaded for the selected levels".into()))?;
+                    ActivePrompt::Kanji { character: kanji.kanji }
+                },
+                ContentMode::KanjiReading => {
+                    let reading_type = self.settings.read(|s| s.reading_type);
+                    self.pick_kanji_reading(level_idx, reading_type, rng)
+                        .ok_or_else(|| AppError::InvalidInput("No kanji readings are loaded for the selected levels".into()))?
+                },
+                ContentMode::Compound => {
+                    self.pick_compound(level_idx, &indices, rng)
+                        .ok_or_else(|| AppError::InternalError("No kanji pairs with a dictionary word for level".into()))?
+                },
+                ContentMode::Vocab => {
+                    let word_map = &self.word_list[level_idx];
+
+                    // Pick a word from the map, favouring common ones in weighted games
+                    let keys = word_map.keys().collect::<Vec<&String>>();
+                    let idx = self.vocab_weights.read(|w| w.get(&level_idx).map(|dist| dist.sample(&mut *rng)))
+                        .unwrap_or_else(|| rng.random_range(0..keys.len()));
+                    let word_key = keys[idx];
+                    let readings = word_map[word_key].clone();
+
+                    ActivePrompt::Vocab {
+                        word: word_key.clone(),
+                        readings,
+                    }
+                },
+                ContentMode::ReverseVocab => {
+                    let spelling_map = &self.spelling_list[level_idx];
+                    if spelling_map.is_empty() {
+                        return Err(AppError::InternalError("No kanji spellings for level".into()));
+                    }
 
-                let prompt = ActivePrompt::Kanji { character: kanji.kanji.clone() };
-                let text = kanji.kanji;
-                self.current_prompt.write(|p| *p = Some(prompt));
+                    let keys = spelling_map.keys().collect::<Vec<&String>>();
+                    let reading = keys[rng.random_range(0..keys.len())];
 
-                text
+                    ActivePrompt::ReverseVocab {
+                        reading: reading.clone(),
+                        words: spelling_map[reading].clone(),
+                    }
+                },
+                ContentMode::Meaning => {
+                    let word_map = &self.word_list[level_idx];
+                    let keys = word_map.keys()
+                        .filter(|w| self.gloss_list.contains_key(*w))
+                        .collect::<Vec<&String>>();
+                    if keys.is_empty() {
+                        return Err(AppError::InvalidInput("No English glosses are loaded for the selected levels".into()));
+                    }
 
-            },
-            ContentMode::Vocab => {
-                let word_map = &self.word_list[level_idx];
+                    let word_key = keys[rng.random_range(0..keys.len())];
+                    // Show the first few senses; the full list is revealed on a miss
+                    let gloss = self.gloss_list[word_key].iter().take(3).cloned().collect::<Vec<_>>().join("; ");
+
+                    ActivePrompt::Meaning {
+                        gloss,
+                        word: word_key.clone(),
+                        readings: word_map[word_key].clone(),
+                    }
+                }
+            }
+        };
 
-                // Pick random word from map
-                let keys = word_map.keys().collect::<Vec<&String>>();
-                let word_key = keys[rng.random_range(0..keys.len())];
-                let readings = word_map[word_key].clone();
+        Ok(prompt)
+    }
 
-                let prompt = ActivePrompt::Vocab {
-                    word: word_key.clone(),
-                    readings,
-                };
-                let text = word_key.clone();
-                self.current_prompt.write(|p| *p = Some(prompt));
+    /// Picks a kanji from `level_idx`, or `None` if the level has none.
+    fn pick_kanji(&self, level_idx: usize, rng: &mut impl rand::Rng) -> Option<crate::data::Kanji> {
+        let kanji_list = self.kanji_list.get(level_idx).filter(|l| !l.is_empty())?;
 
-                text
+        let weighted = self.level_weights.read(|w| w.get(&level_idx).map(|dist| dist.sample(rng)));
+        let idx = weighted.unwrap_or_else(|| rng.random_range(0..kanji_list.len()));
+        Some(kanji_list[idx].clone())
+    }
+
+    /// Picks a kanji from `level_idx` that has readings of `reading_type` and prompts for them.
+    fn pick_kanji_reading(&self, level_idx: usize, reading_type: shared::ReadingType, rng: &mut impl rand::Rng) -> Option<ActivePrompt> {
+        const MAX_ATTEMPTS: usize = 20;
+
+        let readings_of = |kanji: &crate::data::Kanji| -> Vec<String> {
+            match reading_type {
+                shared::ReadingType::Onyomi => kanji.readings.on.clone(),
+                shared::ReadingType::Kunyomi => kanji.readings.kun.clone(),
+                shared::ReadingType::Any => kanji.readings.on.iter().chain(&kanji.readings.kun).cloned().collect(),
             }
         };
 
+        // Keep the level's weighting when possible, otherwise settle for any kanji with readings
+        let kanji = (0..MAX_ATTEMPTS)
+            .filter_map(|_| self.pick_kanji(level_idx, rng))
+            .find(|k| !readings_of(k).is_empty())
+            .or_else(|| {
+                let candidates: Vec<&crate::data::Kanji> = self.kanji_list[level_idx].iter()
+                    .filter(|k| !readings_of(k).is_empty())
+                    .collect();
+                (!candidates.is_empty()).then(|| candidates[rng.random_range(0..candidates.len())].clone())
+            })?;
+
+        Some(ActivePrompt::KanjiReading { readings: readings_of(&kanji), character: kanji.kanji })
+    }
+
+    /// Picks a kanji from `level_idx` and pairs it with another kanji from the active
+    /// levels that appears alongside it in at least one dictionary word.
+    fn pick_compound(&self, level_idx: usize, indices: &[usize], rng: &mut impl rand::Rng) -> Option<ActivePrompt> {
+        const MAX_ATTEMPTS: usize = 20;
+
+        let full_dictionary = self.settings.read(|s| s.full_dictionary);
+        let level_kanji: HashSet<char> = indices.iter()
+            .flat_map(|&i| self.kanji_list[i].iter())
+            .filter_map(|k| k.kanji.chars().next())
+            .collect();
+
+        // A few random draws keep the level's weighting; fall back to trying every kanji once
+        let mut fallback: Vec<String> = self.kanji_list[level_idx].iter().map(|k| k.kanji.clone()).collect();
+        {
+            use rand::seq::SliceRandom;
+            fallback.shuffle(rng);
+        }
+        let draws: Vec<String> = (0..MAX_ATTEMPTS).filter_map(|_| self.pick_kanji(level_idx, rng)).map(|k| k.kanji).collect();
+
+        for first in draws.into_iter().chain(fallback) {
+            let Some(first_char) = first.chars().next() else { continue };
+
+            // Only the words containing this kanji, through the kanji index; sorted so seeded draws repeat
+            let partners: Vec<char> = self.dict_list.words_with_kanji(first_char)
+                .filter(|e| full_dictionary || !e.filtered)
+                .flat_map(|e| e.word.chars())
+                .filter(|c| *c != first_char && level_kanji.contains(c))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+
+            if partners.is_empty() {
+                continue;
+            }
+            let second = partners[rng.random_range(0..partners.len())];
+            return Some(ActivePrompt::Compound { characters: vec![first, second.to_string()] });
+        }
+
+        None
+    }
+
+    /// Store `prompt` as the current prompt and return its display text.
+    /// `broadcast` and `reset_timer` behave as in `generate_random_prompt`.
+    pub fn set_prompt(&self, prompt: ActivePrompt, broadcast: bool, reset_timer: bool) -> String {
+        let display_text = prompt.display_text();
+        self.current_prompt.write(|p| *p = Some(prompt));
+
         if reset_timer {
             self.prompt_counter.write(|c| *c += 1);
             self.skip_votes.write(|v| v.clear());
+            self.review_misses.write(|m| m.clear());
 
-            let time_limit = self.settings.read(|s| s.time_limit_seconds);
-            if let Some(secs) = time_limit {
+            let (mode, time_limit) = self.settings.read(|s| (s.mode, s.time_limit_seconds));
+            if mode.is_timed_run() {
+                // The run's single countdown keeps going across prompts
+            } else if mode == shared::GameMode::Bomb {
+                // The fuse is the only clock and clients never get to see it
+                self.timer_expires_at.write(|t| *t = None);
+            } else if let Some(secs) = time_limit {
                 let counter = self.prompt_counter.read(|c| *c);
                 let expires = Utc::now().timestamp_millis() as u64 + (secs as u64 * 1000);
                 self.timer_expires_at.write(|t| *t = Some(expires));
@@ -537,10 +1066,11 @@ impl LobbyState {
             self.broadcast(shared::ServerMessage::PromptUpdate {
                 new_prompt: display_text.clone(),
                 timer_expires_at: self.timer_expires_at.read(|t| *t),
+                kanji: self.get_current_prompt_kanji(),
             });
         }
 
-        Ok(display_text)
+        display_text
     }
 
     pub fn reset_lobby(&self, player_id: &PlayerId) -> Result<()> {
@@ -557,11 +1087,278 @@ impl LobbyState {
             status: GameStatus::Lobby,
             scores: self.get_all_players(),
             timer_expires_at: None,
+            tier: self.get_current_tier(),
+            kanji: Vec::new(),
         });
 
         Ok(())
     }
 
+    /// Marks the game finished and, in a series, scores it and queues up the next game.
+    fn finish_game(&self) {
+        self.game_status.write(|st| *st = GameStatus::Finished);
+        self.broadcast(shared::ServerMessage::GameState {
+            prompt: self.get_current_prompt_text().unwrap_or_default(),
+            status: GameStatus::Finished,
+            scores: self.get_all_players(),
+            timer_expires_at: None,
+            tier: self.get_current_tier(),
+            kanji: self.get_current_prompt_kanji(),
+        });
+
+        if !self.settings.read(|s| s.is_series()) {
+            return;
+        }
+        let Some(winner) = self.game_winner() else {
+            return;
+        };
+        let name = self.get_player_name(&winner).unwrap_or_default();
+        let Some(series) = self.series.write(|series| {
+            series.as_mut().map(|s| { s.record_win(winner, name); s.clone() })
+        }) else {
+            return;
+        };
+        self.broadcast(shared::ServerMessage::SeriesUpdate { series: Some(series.clone()) });
+
+        if series.champion.is_none() {
+            let lobby = self.clone();
+            let games_played = series.games_played;
+            tokio::spawn(async move {
+                tokio::time::sleep(tokio::time::Duration::from_secs(SERIES_NEXT_GAME_DELAY_SECS)).await;
+                lobby.start_next_series_game(games_played);
+            });
+        }
+    }
+
+    /// The winner of the game that just finished: the top scorer, and in turn-based modes
+    /// the top scorer among players still standing.
+    fn game_winner(&self) -> Option<PlayerId> {
+        let turn_based = self.settings.read(|s| s.mode.is_turn_based());
+        self.get_all_players().into_iter()
+            .filter(|p| !p.is_spectator)
+            .max_by_key(|p| (turn_based && !p.is_eliminated, p.score))
+            .map(|p| p.id)
+    }
+
+    /// Starts the next game of a series, unless the lobby has moved on since game
+    /// `expected_games_played` finished (e.g. the leader went back to the lobby).
+    pub fn start_next_series_game(&self, expected_games_played: u32) {
+        if self.game_status.read(|s| *s) != GameStatus::Finished {
+            return;
+        }
+        let still_running = self.series.read(|s| {
+            s.as_ref().is_some_and(|s| s.champion.is_none() && s.games_played == expected_games_played)
+        });
+        if !still_running {
+            return;
+        }
+
+        self.game_status.write(|st| *st = GameStatus::Lobby);
+        let leader = self.lobby_leader.read(|l| l.clone());
+        if let Err(e) = self.start_game(&leader) {
+            tracing::warn!("Could not start the next series game: {:?}", e);
+            self.broadcast(shared::ServerMessage::GameState {
+                prompt: "".to_string(),
+                status: GameStatus::Lobby,
+                scores: self.get_all_players(),
+                timer_expires_at: None,
+                tier: self.get_current_tier(),
+                kanji: Vec::new(),
+            });
+        }
+    }
+
+    /// Starts the one countdown that spans a whole timed run.
+    fn start_time_attack_clock(&self, secs: u32) {
+        let expires = Utc::now().timestamp_millis() as u64 + (secs as u64 * 1000);
+        self.timer_expires_at.write(|t| *t = Some(expires));
+
+        let lobby = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_secs(secs as u64)).await;
+            lobby.finish_time_attack(expires);
+        });
+    }
+
+    /// Ends the run started with deadline `expected_expires`, unless the game has since moved on.
+    pub fn finish_time_attack(&self, expected_expires: u64) {
+        let status = self.game_status.read(|s| *s);
+        let current_expires = self.timer_expires_at.read(|t| *t);
+        if status != GameStatus::Playing || current_expires != Some(expected_expires) {
+            return;
+        }
+
+        self.timer_expires_at.write(|t| *t = None);
+        self.finish_game();
+
+        if let Some(pool) = self.db_pool.clone() {
+            let lobby = self.clone();
+            let mode = self.settings.read(|s| s.mode);
+            tokio::spawn(async move {
+                if mode == shared::GameMode::Daily {
+                    lobby.record_daily_challenge_runs(&pool).await;
+                } else {
+                    lobby.record_time_attack_runs(&pool).await;
+                }
+            });
+        }
+    }
+
+    /// Non-spectating players whose socket logged in to a registered account, alongside their final score.
+    /// Runs are never matched up by name, since anyone can join under someone else's.
+    async fn registered_players(&self, pool: &crate::db::DbPool) -> Vec<(PlayerId, crate::models::User, u32)> {
+        let runs: Vec<(PlayerId, uuid::Uuid, u32)> = self.players.read(|players| {
+            players.iter()
+                .filter(|p| !p.is_spectator && p.bot.is_none())
+                .filter_map(|p| Some((p.id.clone(), p.user_id?, p.score)))
+                .collect()
+        });
+
+        let mut registered = Vec::new();
+        for (player_id, user_id, score) in runs {
+            match crate::models::User::find_by_id(pool, user_id).await {
+                Ok(Some(user)) if !user.is_guest => registered.push((player_id, user, score)),
+                Ok(_) => {},
+                Err(e) => tracing::error!("Failed to look up account {} to record their run: {}", user_id, e),
+            }
+        }
+        registered
+    }
+
+    /// Saves each logged-in player's run and tells them how it compares to their personal best.
+    async fn record_time_attack_runs(&self, pool: &crate::db::DbPool) {
+        let settings = self.settings.read(|s| s.clone());
+        let key = crate::models::TimeAttackKey::new(
+            &settings.difficulty_levels,
+            &settings.content_mode,
+            settings.timer_seconds().unwrap_or(shared::DEFAULT_TIME_ATTACK_SECONDS),
+        );
+
+        for (player_id, user, score) in self.registered_players(pool).await {
+            let previous_best = crate::models::TimeAttackRun::personal_best(pool, user.id, &key).await.unwrap_or(None);
+            if let Err(e) = crate::models::TimeAttackRun::create(pool, user.id, &key, score as i32).await {
+                tracing::error!("Failed to record time attack run for {}: {}", user.username, e);
+                continue;
+            }
+
+            let previous_best = previous_best.map(|b| b.max(0) as u32);
+            self.broadcast(shared::ServerMessage::TimeAttackResult {
+                player_id,
+                result: shared::TimeAttackResult {
+                    score,
+                    personal_best: previous_best.map_or(score, |b| b.max(score)),
+                    is_new_best: previous_best.is_none_or(|b| score > b),
+                },
+            });
+        }
+    }
+
+    /// Saves each logged-in player's first daily-challenge run of the day and tells them where it placed.
+    async fn record_daily_challenge_runs(&self, pool: &crate::db::DbPool) {
+        let Some(date) = self.daily_date.read(|d| *d) else { return };
+
+        for (player_id, user, score) in self.registered_players(pool).await {
+            let recorded = match crate::models::DailyChallengeRun::create(pool, user.id, date, score as i32).await {
+                Ok(recorded) => recorded,
+                Err(e) => {
+                    tracing::error!("Failed to record daily challenge run for {}: {}", user.username, e);
+                    continue;
+                }
+            };
+
+            let rank = match recorded {
+                Some(_) => crate::models::DailyChallengeRun::rank(pool, date, score as i32).await.ok().map(|r| r as u32),
+                None => None,
+            };
+            self.broadcast(shared::ServerMessage::DailyChallengeResult {
+                player_id,
+                result: shared::DailyChallengeResult {
+                    score,
+                    rank,
+                    is_scored: recorded.is_some(),
+                },
+            });
+        }
+    }
+
+    /// Reschedules `prompt` in the player's review queue if they have an account.
+    /// A miss counts once per prompt, and a correct answer only in Review mode after no miss.
+    fn record_review(&self, player_id: &PlayerId, prompt: &ActivePrompt, correct: bool) {
+        let Some(pool) = self.db_pool.clone() else { return };
+        // A chain link only makes sense after the word before it
+        if matches!(prompt, ActivePrompt::Shiritori { .. }) {
+            return;
+        }
+        let already_missed = self.review_misses.write(|m| if correct { m.contains(player_id) } else { !m.insert(player_id.clone()) });
+        if already_missed || (correct && self.settings.read(|s| s.mode) != shared::GameMode::Review) {
+            return;
+        }
+        let Some(user_id) = self.get_player_account(player_id) else {
+            return;
+        };
+
+        let prompt = prompt.clone();
+        tokio::spawn(async move {
+            let user = match crate::models::User::find_by_id(&pool, user_id).await {
+                Ok(Some(user)) if !user.is_guest => user,
+                Ok(_) => return,
+                Err(e) => {
+                    tracing::error!("Failed to look up account {} to record a review: {}", user_id, e);
+                    return;
+                }
+            };
+            if let Err(e) = crate::models::ReviewItem::review(&pool, user.id, &prompt, correct).await {
+                tracing::error!("Failed to record review for {}: {}", user.username, e);
+            }
+        });
+    }
+
+    /// Lights a new Bomb fuse that burns for a random time within the configured range.
+    /// The length stays on the server; clients only hear about it when it goes off.
+    fn light_fuse(&self) {
+        let (min, max) = self.settings.read(|s| s.fuse_range());
+        let millis = rand::rng().random_range(min as u64 * 1000..=max as u64 * 1000);
+        let generation = self.fuse_generation.write(|g| { *g += 1; *g });
+
+        let lobby = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(millis)).await;
+            lobby.process_explosion(generation);
+        });
+    }
+
+    /// The fuse lit as `expected_generation` has run out: whoever holds the prompt loses a life
+    /// and the next player gets a fresh prompt and a freshly lit fuse.
+    pub fn process_explosion(&self, expected_generation: u64) {
+        if self.fuse_generation.read(|g| *g) != expected_generation {
+            return; // A newer fuse has been lit since
+        }
+        if self.game_status.read(|s| *s) != GameStatus::Playing {
+            return;
+        }
+        let Some(player_id) = self.get_current_turn_player() else {
+            return;
+        };
+
+        let mut new_prompt_opt = None;
+        let mut game_over = false;
+        let (eliminated, _) = self.apply_duel_penalty(&player_id, &mut new_prompt_opt, &mut game_over);
+
+        self.broadcast(shared::ServerMessage::BombExploded {
+            player_id,
+            eliminated,
+        });
+        self.broadcast(shared::ServerMessage::PlayerListUpdate {
+            players: self.get_all_players()
+        });
+
+        if game_over {
+            self.finish_game();
+        } else {
+            self.light_fuse();
+        }
+    }
+
     pub fn advance_turn(&self) -> Result<PlayerId> {
         self.turn_order.write(|order| {
              self.current_turn_index.write(|idx| {
@@ -596,7 +1393,7 @@ impl LobbyState {
             return Ok(());
         }
 
-        if settings.mode == shared::GameMode::Duel {
+        if settings.mode.is_turn_based() {
             let current_turn = self.get_current_turn_player();
             if current_turn.as_ref() != Some(player_id) {
                 return Ok(()); // Not your turn
@@ -604,22 +1401,47 @@ impl LobbyState {
         }
 
 
-        let trimmed_input = input.trim();
         let prompt = self.current_prompt.read(|p| p.clone())
             .ok_or(AppError::InternalError("No active prompt".into()))?;
+        let mut answer = clean_answer(input, settings.answer_strictness);
+        if settings.allow_romaji && matches!(prompt, ActivePrompt::Vocab { .. }) {
+            // Romaji is converted before punctuation is trimmed, since `-` and `'` spell ー and ん
+            let kana = crate::romaji::romaji_to_kana(input.trim())
+                .or_else(|| crate::romaji::romaji_to_kana(&answer));
+            if let Some(kana) = kana {
+                answer = clean_answer(&kana, settings.answer_strictness);
+            }
+        }
+        let trimmed_input = answer.as_str();
+
 
+        let mut accepted = check_prompt(&prompt, trimmed_input, &self.dict_list, &self.reading_list, settings.answer_strictness, settings.exact_headwords, settings.full_dictionary);
+        let mut is_correct = accepted.is_some();
 
-        let is_correct = check_prompt(&prompt, trimmed_input, &self.dict_list);
+        // Shiritori words must also be unused this game and must not end in ん
+        let mut already_used = false;
+        let mut chain_reading = None;
+        if let ActivePrompt::Shiritori { reading, .. } = &prompt {
+            already_used = self.used_words.read(|w| w.contains(trimmed_input));
+            chain_reading = shiritori_reading(reading, trimmed_input, &self.reading_list).map(str::to_string);
+            if already_used || chain_reading.as_deref().is_some_and(ends_with_n) {
+                is_correct = false;
+                accepted = None;
+            }
+        }
 
         let mut message = String::new();
         let mut new_prompt_opt = None;
         let mut game_over = false;
         let mut error_details = None;
+        let mut suggestions = None;
+        let mut missed_kanji = Vec::new();
+        let mut tier_changed = false;
         if is_correct {
             let new_score = self.increment_player_score(player_id)?;
 
             let username = self.players.read(|players| {
-                players.iter().find(|p| p.id == *player_id).map(|p| p.name.clone())
+                players.iter().find(|p| p.id == *player_id && p.bot.is_none()).map(|p| p.name.clone())
             });
 
             if let (Some(pool), Some(name)) = (self.db_pool.clone(), username) {
@@ -631,32 +1453,65 @@ impl LobbyState {
 
             if settings.mode == shared::GameMode::Deathmatch {
                 if let Some(target) = settings.target_score {
-                    if new_score >= target {
+                    // Team players race on their pooled total instead of their own score
+                    let team = settings.team_of(player_id);
+                    let standing = team.map(|t| self.get_team_score(&t.name)).unwrap_or(new_score);
+                    if standing >= target {
                         game_over = true;
-                        message = "Winner!".to_string();
+                        message = match team {
+                            Some(t) => format!("{} wins!", t.name),
+                            None => "Winner!".to_string(),
+                        };
                     } else {
                         message = "Good guess!".to_string();
                         let _ = self.generate_random_prompt(true, true);
                         new_prompt_opt = self.get_current_prompt_text();
                     }
                 }
-            } else if settings.mode == shared::GameMode::Duel {
+            } else if settings.mode == shared::GameMode::Survival {
+                message = "Good guess!".to_string();
+                if let Some(tier) = self.climb_survival_ladder() {
+                    message = format!("Good guess! Level up: {}", tier);
+                    tier_changed = true;
+                }
+                let _ = self.generate_random_prompt(true, true);
+                new_prompt_opt = self.get_current_prompt_text();
+                self.reuse_prompt.write(|r| *r = false);
+                let _ = self.advance_turn();
+            } else if settings.mode == shared::GameMode::Duel || settings.mode == shared::GameMode::Bomb {
+                // In Bomb the turn passing on hands the prompt, and the fuse with it, to the next player
                 message = "Good guess!".to_string();
                 let _ = self.generate_random_prompt(true, true);
                 new_prompt_opt = self.get_current_prompt_text();
                 self.reuse_prompt.write(|r| *r = false);
                 let _ = self.advance_turn();
-            } else if settings.mode == shared::GameMode::Zen {
+            } else if matches!(settings.mode, shared::GameMode::Zen | shared::GameMode::Review) || settings.mode.is_timed_run() {
+                if settings.mode == shared::GameMode::Review {
+                    self.record_review(player_id, &prompt, true);
+                }
                 message = "Good guess!".to_string();
                 let _ = self.generate_random_prompt(true, true);
                 new_prompt_opt = self.get_current_prompt_text();
+            } else if settings.mode == shared::GameMode::Shiritori {
+                message = "Good guess!".to_string();
+                if let Some(reading) = chain_reading {
+                    self.used_words.write(|w| { w.insert(trimmed_input.to_string()); });
+                    // The answer becomes the next link in the chain
+                    new_prompt_opt = Some(self.set_prompt(ActivePrompt::Shiritori {
+                        word: trimmed_input.to_string(),
+                        reading,
+                    }, true, true));
+                }
+                let _ = self.advance_turn();
             }
         } else {
-            error_details = self.get_error_details();
+            self.record_review(player_id, &prompt, false);
+            (error_details, suggestions) = self.get_error_details();
+            missed_kanji = self.prompt_kanji(&prompt, true);
             match &prompt {
                 ActivePrompt::Kanji { character } => {
                     let has_kanji = trimmed_input.contains(character.as_str());
-                    let valid_word = self.dict_list.contains(trimmed_input);
+                    let valid_word = self.dict_list.lookup(trimmed_input, settings.full_dictionary).is_some();
                     if has_kanji {
                         message = "Bad Guess: Correct kanji, but not a valid word".to_string();
                     } else if valid_word {
@@ -665,9 +1520,37 @@ impl LobbyState {
                         message = "Bad Guess: Incorrect kanji and not a valid word".to_string();
                     }
                 },
-                ActivePrompt::Vocab { word, .. } => { message = format!("Incorrect reading for {}", word); }
+                ActivePrompt::Compound { characters } => {
+                    let has_all = characters.iter().all(|c| trimmed_input.contains(c.as_str()));
+                    if has_all {
+                        message = "Bad Guess: Contains both kanji, but not a valid word".to_string();
+                    } else if self.dict_list.lookup(trimmed_input, settings.full_dictionary).is_some() {
+                        message = "Bad Guess: Valid word, but does not contain both kanji.".to_string();
+                    } else {
+                        message = "Bad Guess: Missing kanji and not a valid word".to_string();
+                    }
+                },
+                ActivePrompt::KanjiReading { character, .. } => { message = format!("Incorrect reading for {}", character); },
+                ActivePrompt::Vocab { word, .. } => { message = format!("Incorrect reading for {}", word); },
+                ActivePrompt::ReverseVocab { reading, .. } => { message = format!("Incorrect spelling for {}", reading); },
+                ActivePrompt::Meaning { gloss, .. } => { message = format!("Incorrect word for \"{}\"", gloss); },
+                ActivePrompt::Shiritori { reading, .. } => {
+                    let tail = shiritori_tail(reading).unwrap_or_default();
+                    if already_used {
+                        message = "Bad Guess: That word has already been used".to_string();
+                    } else if chain_reading.is_some() {
+                        message = "Bad Guess: Ends in ん!".to_string();
+                    } else if self.reading_list.contains_key(trimmed_input) {
+                        message = format!("Bad Guess: Reading must start with {}", tail);
+                    } else {
+                        message = "Bad Guess: Not a known word".to_string();
+                    }
+                }
             }
-            if settings.mode == shared::GameMode::Duel {
+            if chain_reading.as_deref().is_some_and(ends_with_n) && !already_used {
+                let duel_message = self.eliminate_player(player_id, &mut new_prompt_opt, &mut game_over);
+                message = format!("{}\n{}", message, duel_message);
+            } else if settings.mode.is_turn_based() && settings.mode != shared::GameMode::Bomb {
                 let (eliminated, duel_message) = self.apply_duel_penalty(player_id, &mut new_prompt_opt, &mut game_over);
                 if eliminated {
                     message = format!("{}\n{}", message, duel_message);
@@ -679,6 +1562,17 @@ impl LobbyState {
             players: self.get_all_players()
         });
 
+        if tier_changed && !game_over {
+            self.broadcast(shared::ServerMessage::GameState {
+                prompt: self.get_current_prompt_text().unwrap_or_default(),
+                status: GameStatus::Playing,
+                scores: self.get_all_players(),
+                timer_expires_at: self.timer_expires_at.read(|t| *t),
+                tier: self.get_current_tier(),
+                kanji: self.get_current_prompt_kanji(),
+            });
+        }
+
         let score = self.get_player_score(player_id).unwrap_or(0);
         self.broadcast(shared::ServerMessage::WordChecked {
             player_id: player_id.clone(),
@@ -687,42 +1581,75 @@ impl LobbyState {
                 score,
                 error: if !is_correct { Some("Incorrect".into()) } else { None },
                 error_details,
+                accepted,
+                suggestions,
+                missed_kanji,
+                prompt_kanji: self.new_prompt_kanji(&new_prompt_opt),
                 prompt: new_prompt_opt,
                 timer_expires_at: self.timer_expires_at.read(|t| *t),
             },
         });
 
         if game_over {
-            self.game_status.write(|st| *st = GameStatus::Finished);
-            self.broadcast(shared::ServerMessage::GameState {
-                prompt: self.get_current_prompt_text().unwrap_or_default(),
-                status: GameStatus::Finished,
-                scores: self.get_all_players(),
-                timer_expires_at: None,
-            });
+            self.finish_game();
         }
 
         Ok(())
     }
 
-    fn get_error_details(&self) -> Option<Vec<String>> {
-        let prompt = self.current_prompt.read(|p| p.clone())?;
-        match prompt {
-            ActivePrompt::Vocab { readings, .. } => Some(readings),
-            ActivePrompt::Kanji { character } => {
+    /// Answers that would have been accepted for the current prompt, as text for the feedback message
+    /// and, where the answers are words, as dictionary entries with the most common first.
+    fn get_error_details(&self) -> (Option<Vec<String>>, Option<Vec<shared::WordInfo>>) {
+        let Some(prompt) = self.current_prompt.read(|p| p.clone()) else {
+            return (None, None);
+        };
+        let suggestions = match prompt {
+            ActivePrompt::Vocab { readings, .. } | ActivePrompt::KanjiReading { readings, .. } => return (Some(readings), None),
+            ActivePrompt::Meaning { word, readings, .. } => {
+                let mut details = vec![format!("{} ({})", word, readings.join("・"))];
+                if let Some(glosses) = self.gloss_list.get(&word) {
+                    details.push(glosses.join("; "));
+                }
+                return (Some(details), Some(vec![self.dict_list.describe(&word, &self.reading_list)]));
+            },
+            ActivePrompt::ReverseVocab { words, .. } => {
+                words.iter().map(|w| self.dict_list.describe(w, &self.reading_list)).collect()
+            },
+            ActivePrompt::Shiritori { reading, .. } => {
+                let Some(tail) = shiritori_tail(&reading) else {
+                    return (None, None);
+                };
+                let used = self.used_words.read(|w| w.clone());
                 let mut matches = Vec::new();
-                for w in self.dict_list.iter() {
-                    if w.contains(&character) {
-                        matches.push(w.clone());
+                for (word, readings) in self.reading_list.iter() {
+                    let continues = readings.iter().any(|r| {
+                        r.chars().next().map(to_hiragana) == Some(tail) && !ends_with_n(r)
+                    });
+                    if continues && !used.contains(word) {
+                        matches.push(self.dict_list.describe(word, &self.reading_list));
                         if matches.len() >= 3 { break; }
                     }
                 }
-                Some(matches)
+                matches
+            },
+            ActivePrompt::Compound { characters } => {
+                let kanji: Vec<char> = characters.iter().filter_map(|c| c.chars().next()).collect();
+                self.dict_list.words_with_all_kanji(&kanji).filter(|e| !e.filtered).take(3).map(DictEntry::info).collect()
+            },
+            ActivePrompt::Kanji { character } => {
+                let Some(kanji) = character.chars().next() else {
+                    return (None, None);
+                };
+                self.dict_list.words_with_kanji(kanji).filter(|e| !e.filtered).take(3).map(DictEntry::info).collect()
             }
-        }
+        };
+        let details = suggestions.iter().map(shared::WordInfo::summary).collect();
+        (Some(details), Some(suggestions))
     }
 
     fn apply_duel_penalty(&self, player_id: &PlayerId, new_prompt_opt: &mut Option<String>, game_over: &mut bool) -> (bool, String) {
+        self.survival_streak.write(|s| *s = 0);
+
         let eliminated = self.players.write(|players| {
              let mut eliminated = false;
              if let Some(p) = players.iter_mut().find(|p| p.id == *player_id) {
@@ -745,7 +1672,10 @@ impl LobbyState {
         }
 
         let settings = self.settings.read(|s| s.clone());
-        if settings.duel_allow_kanji_reuse {
+        if settings.mode == shared::GameMode::Shiritori {
+            // The chain carries on from the same word for the next player
+            self.restart_current_prompt(new_prompt_opt);
+        } else if settings.duel_allow_kanji_reuse {
             let already_reused = self.reuse_prompt.read(|r| *r);
             if already_reused {
                 let _ = self.generate_random_prompt(true, true);
@@ -760,21 +1690,12 @@ impl LobbyState {
         }
 
         if eliminated {
-             self.turn_order.write(|order| {
-                 if let Some(pos) = order.iter().position(|id| id == player_id) {
-                     order.remove(pos);
-                     self.current_turn_index.write(|idx| {
-                         if *idx >= order.len() && !order.is_empty() {
-                             *idx = 0;
-                         }
-                     })
-                 }
-            });
+             self.remove_from_turn_order(player_id);
         } else {
              let _ = self.advance_turn();
         }
         let order_len = self.turn_order.read(|o| o.len());
-        if order_len <= 1 {
+        if self.is_last_standing(order_len) {
             *game_over = true;
             if !eliminated {
                  msg = "Winner!".to_string();
@@ -783,6 +1704,44 @@ impl LobbyState {
         (eliminated, msg)
     }
 
+    /// Knocks a player out immediately, regardless of how many lives they have left.
+    fn eliminate_player(&self, player_id: &PlayerId, new_prompt_opt: &mut Option<String>, game_over: &mut bool) -> String {
+        self.players.write(|players| {
+            if let Some(p) = players.iter_mut().find(|p| p.id == *player_id) {
+                p.lives = Some(0);
+                p.is_eliminated = true;
+            }
+        });
+
+        self.restart_current_prompt(new_prompt_opt);
+        self.remove_from_turn_order(player_id);
+
+        if self.is_last_standing(self.turn_order.read(|o| o.len())) {
+            *game_over = true;
+        }
+        "Eliminated!".to_string()
+    }
+
+    fn remove_from_turn_order(&self, player_id: &PlayerId) {
+        self.turn_order.write(|order| {
+            if let Some(pos) = order.iter().position(|id| id == player_id) {
+                order.remove(pos);
+                self.current_turn_index.write(|idx| {
+                    if *idx >= order.len() && !order.is_empty() {
+                        *idx = 0;
+                    }
+                })
+            }
+        });
+    }
+
+    /// Re-issues the current prompt with a fresh timer.
+    fn restart_current_prompt(&self, new_prompt_opt: &mut Option<String>) {
+        if let Some(prompt) = self.current_prompt.read(|p| p.clone()) {
+            *new_prompt_opt = Some(self.set_prompt(prompt, true, true));
+        }
+    }
+
     pub fn process_timeout(&self, expected_counter: u64) {
         let current_counter = self.prompt_counter.read(|c| *c);
         if current_counter != expected_counter {
@@ -795,9 +1754,22 @@ impl LobbyState {
         }
 
         let settings = self.settings.read(|s| s.clone());
-        let error_details = self.get_error_details();
+        let (error_details, suggestions) = self.get_error_details();
+        let missed_kanji = self.current_prompt.read(|p| p.as_ref().map(|prompt| self.prompt_kanji(prompt, true)).unwrap_or_default());
+
+        if let Some(prompt) = self.current_prompt.read(|p| p.clone()) {
+            // Running out of time counts as a miss for whoever was meant to answer
+            let missed: Vec<PlayerId> = if settings.mode.is_turn_based() {
+                self.get_current_turn_player().into_iter().collect()
+            } else {
+                self.get_all_players().into_iter().filter(|p| !p.is_eliminated && !p.is_spectator).map(|p| p.id).collect()
+            };
+            for player_id in &missed {
+                self.record_review(player_id, &prompt, false);
+            }
+        }
 
-        if settings.mode == shared::GameMode::Duel {
+        if settings.mode.is_turn_based() {
             if let Some(player_id) = self.get_current_turn_player() {
                 let mut new_prompt_opt = None;
                 let mut game_over = false;
@@ -817,19 +1789,17 @@ impl LobbyState {
                         score,
                         error: Some("Time's up!".into()),
                         error_details,
+                        accepted: None,
+                        suggestions,
+                        missed_kanji,
                         prompt: new_prompt_opt.clone(),
+                        prompt_kanji: self.new_prompt_kanji(&new_prompt_opt),
                         timer_expires_at: self.timer_expires_at.read(|t| *t),
                     },
                 });
 
                 if game_over {
-                    self.game_status.write(|st| *st = GameStatus::Finished);
-                    self.broadcast(shared::ServerMessage::GameState {
-                        prompt: self.get_current_prompt_text().unwrap_or_default(),
-                        status: GameStatus::Finished,
-                        scores: self.get_all_players(),
-                        timer_expires_at: None,
-                    });
+                    self.finish_game();
                 }
             }
         } else {
@@ -842,7 +1812,11 @@ impl LobbyState {
                     score: 0,
                     error: Some("Time's up!".into()),
                     error_details,
+                    accepted: None,
+                    suggestions,
+                    missed_kanji,
                     prompt: self.get_current_prompt_text(),
+                    prompt_kanji: self.get_current_prompt_kanji(),
                     timer_expires_at: self.timer_expires_at.read(|t| *t),
                 },
             });
@@ -861,13 +1835,38 @@ impl LobbyState {
         }
 
         let settings = self.settings.read(|s| s.clone());
-        let error_details = self.get_error_details();
+        let (error_details, suggestions) = self.get_error_details();
+        let missed_kanji = self.current_prompt.read(|p| p.as_ref().map(|prompt| self.prompt_kanji(prompt, true)).unwrap_or_default());
 
-        if settings.mode == shared::GameMode::Duel {
+        if settings.mode.is_turn_based() {
             let current_turn = self.get_current_turn_player();
             if current_turn.as_ref() != Some(player_id) {
                 return Ok(()); // Handled only if it's your turn
             }
+            if let Some(prompt) = self.current_prompt.read(|p| p.clone()) {
+                self.record_review(player_id, &prompt, false);
+            }
+
+            if settings.mode == shared::GameMode::Bomb {
+                // A fresh prompt, but the bomb stays in the same hands
+                let _ = self.generate_random_prompt(true, true);
+                self.broadcast(shared::ServerMessage::WordChecked {
+                    player_id: player_id.clone(),
+                    result: shared::CheckWordResponse {
+                        message: "Skipped! You're still holding the bomb.".to_string(),
+                        score: self.get_player_score(player_id).unwrap_or(0),
+                        error: Some("Skipped!".into()),
+                        error_details,
+                        accepted: None,
+                        suggestions,
+                        missed_kanji,
+                        prompt: self.get_current_prompt_text(),
+                        prompt_kanji: self.get_current_prompt_kanji(),
+                        timer_expires_at: None,
+                    },
+                });
+                return Ok(());
+            }
 
             let mut new_prompt_opt = None;
             let mut game_over = false;
@@ -887,23 +1886,24 @@ impl LobbyState {
                     score,
                     error: Some("Skipped!".into()),
                     error_details,
+                    accepted: None,
+                    suggestions,
+                    missed_kanji,
+                    prompt_kanji: self.new_prompt_kanji(&new_prompt_opt),
                     prompt: new_prompt_opt,
                     timer_expires_at: self.timer_expires_at.read(|t| *t),
                 },
             });
 
             if game_over {
-                self.game_status.write(|st| *st = GameStatus::Finished);
-                self.broadcast(shared::ServerMessage::GameState {
-                    prompt: self.get_current_prompt_text().unwrap_or_default(),
-                    status: GameStatus::Finished,
-                    scores: self.get_all_players(),
-                    timer_expires_at: None,
-                });
+                self.finish_game();
             }
 
         } else {
             // Deathmatch skipping requires majority vote
+            if let Some(prompt) = self.current_prompt.read(|p| p.clone()) {
+                self.record_review(player_id, &prompt, false);
+            }
             let mut skip_passed = false;
             let (votes, required) = self.skip_votes.write(|votes| {
                 votes.insert(player_id.clone());
@@ -925,7 +1925,11 @@ impl LobbyState {
                         score: 0,
                         error: Some("Skipped!".into()),
                         error_details,
+                        accepted: None,
+                        suggestions,
+                        missed_kanji,
                         prompt: self.get_current_prompt_text(),
+                        prompt_kanji: self.get_current_prompt_kanji(),
                         timer_expires_at: self.timer_expires_at.read(|t| *t),
                     },
                 });
@@ -971,6 +1975,8 @@ impl LobbyState {
                 status: GameStatus::Lobby,
                 scores: self.get_all_players(),
                 timer_expires_at: None,
+                tier: self.get_current_tier(),
+                kanji: Vec::new(),
             });
         } else {
             let score = self.get_player_score(player_id).unwrap_or(0);
@@ -981,7 +1987,11 @@ impl LobbyState {
                     score,
                     error: Some(format!("Return to Lobby vote registered ({}/{})", votes, required)),
                     error_details: None,
+                    accepted: None,
+                    suggestions: None,
+                    missed_kanji: Vec::new(),
                     prompt: None,
+                    prompt_kanji: Vec::new(),
                     timer_expires_at: self.timer_expires_at.read(|t| *t),
                 },
             });
@@ -0,0 +1,72 @@
+use crate::db::DbPool;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct DailyChallengeRun {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub challenge_date: NaiveDate,
+    pub score: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct DailyChallengeStanding {
+    pub username: String,
+    pub score: i32,
+}
+
+impl DailyChallengeRun {
+    /// Record the user's attempt for `date`. Returns `None` if they already have one, since only the first counts.
+    pub async fn create(pool: &DbPool, user_id: Uuid, date: NaiveDate, score: i32) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, DailyChallengeRun>(
+            r#"
+            INSERT INTO daily_challenge_runs (user_id, challenge_date, score)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, challenge_date) DO NOTHING
+            RETURNING id, user_id, challenge_date, score, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(date)
+        .bind(score)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// 1-based place a score would take on the day's leaderboard; ties share a rank
+    pub async fn rank(pool: &DbPool, date: NaiveDate, score: i32) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) + 1
+            FROM daily_challenge_runs
+            WHERE challenge_date = $1 AND score > $2
+            "#,
+        )
+        .bind(date)
+        .bind(score)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Best scores for the day, earliest run first among ties
+    pub async fn leaderboard(pool: &DbPool, date: NaiveDate, limit: i64) -> Result<Vec<DailyChallengeStanding>, sqlx::Error> {
+        sqlx::query_as::<_, DailyChallengeStanding>(
+            r#"
+            SELECT u.username, r.score
+            FROM daily_challenge_runs r
+            JOIN users u ON u.id = r.user_id
+            WHERE r.challenge_date = $1
+            ORDER BY r.score DESC, r.created_at ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(date)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+}
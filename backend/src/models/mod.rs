@@ -2,7 +2,13 @@
 pub mod game;
 pub mod user;
 pub mod stats;
+pub mod time_attack;
+pub mod daily_challenge;
+pub mod review_item;
 
 pub use game::{GameAction, GameSession, PlayerStats};
 pub use user::User;
 pub use stats::GlobalStats;
+pub use time_attack::{TimeAttackKey, TimeAttackRun};
+pub use daily_challenge::{DailyChallengeRun, DailyChallengeStanding};
+pub use review_item::ReviewItem;
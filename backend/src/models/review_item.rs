@@ -0,0 +1,125 @@
+use crate::{db::DbPool, review::{prompt_key, Schedule}};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use shared::ActivePrompt;
+use sqlx::{FromRow, types::Json};
+use uuid::Uuid;
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct ReviewItem {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub prompt: Json<ActivePrompt>,
+    pub ease_factor: f64,
+    pub interval_days: i32,
+    pub repetitions: i32,
+    pub lapses: i32,
+    pub due_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReviewItem {
+    pub fn schedule(&self) -> Schedule {
+        Schedule {
+            ease_factor: self.ease_factor,
+            interval_days: self.interval_days.max(0) as u32,
+            repetitions: self.repetitions.max(0) as u32,
+            lapses: self.lapses.max(0) as u32,
+        }
+    }
+
+    pub async fn find(pool: &DbPool, user_id: Uuid, prompt: &ActivePrompt) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ReviewItem>(
+            r#"
+            SELECT id, user_id, prompt, ease_factor, interval_days, repetitions, lapses, due_at, created_at, updated_at
+            FROM review_items
+            WHERE user_id = $1 AND prompt_key = $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(prompt_key(prompt))
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Reschedule `prompt` after the user answered it (`correct`) or missed or skipped it.
+    /// A miss adds the prompt to the queue; a correct answer only moves along prompts already in it.
+    pub async fn review(pool: &DbPool, user_id: Uuid, prompt: &ActivePrompt, correct: bool) -> Result<Option<Self>, sqlx::Error> {
+        let existing = Self::find(pool, user_id, prompt).await?;
+        if correct && existing.is_none() {
+            return Ok(None);
+        }
+
+        let schedule = existing.map(|item| item.schedule()).unwrap_or_default().review(correct);
+        let due_at = Utc::now() + Duration::days(schedule.interval_days as i64);
+
+        sqlx::query_as::<_, ReviewItem>(
+            r#"
+            INSERT INTO review_items (user_id, prompt, prompt_key, ease_factor, interval_days, repetitions, lapses, due_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (user_id, prompt_key) DO UPDATE SET
+                prompt = EXCLUDED.prompt,
+                ease_factor = EXCLUDED.ease_factor,
+                interval_days = EXCLUDED.interval_days,
+                repetitions = EXCLUDED.repetitions,
+                lapses = EXCLUDED.lapses,
+                due_at = EXCLUDED.due_at,
+                updated_at = NOW()
+            RETURNING id, user_id, prompt, ease_factor, interval_days, repetitions, lapses, due_at, created_at, updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(Json(prompt))
+        .bind(prompt_key(prompt))
+        .bind(schedule.ease_factor)
+        .bind(schedule.interval_days as i32)
+        .bind(schedule.repetitions as i32)
+        .bind(schedule.lapses as i32)
+        .bind(due_at)
+        .fetch_one(pool)
+        .await
+        .map(Some)
+    }
+
+    /// Prompts whose review is due, most overdue first
+    pub async fn due(pool: &DbPool, user_id: Uuid, limit: i64) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ReviewItem>(
+            r#"
+            SELECT id, user_id, prompt, ease_factor, interval_days, repetitions, lapses, due_at, created_at, updated_at
+            FROM review_items
+            WHERE user_id = $1 AND due_at <= NOW()
+            ORDER BY due_at ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The user's whole queue in the order it comes due
+    pub async fn list(pool: &DbPool, user_id: Uuid) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as::<_, ReviewItem>(
+            r#"
+            SELECT id, user_id, prompt, ease_factor, interval_days, repetitions, lapses, due_at, created_at, updated_at
+            FROM review_items
+            WHERE user_id = $1
+            ORDER BY due_at ASC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Clears the user's queue, returning how many prompts were in it
+    pub async fn reset(pool: &DbPool, user_id: Uuid) -> Result<u64, sqlx::Error> {
+        sqlx::query("DELETE FROM review_items WHERE user_id = $1")
+            .bind(user_id)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+    }
+}
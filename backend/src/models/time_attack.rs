@@ -0,0 +1,74 @@
+use crate::db::DbPool;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct TimeAttackRun {
+    pub id: i64,
+    pub user_id: Uuid,
+    pub level_set: String,
+    pub content_mode: String,
+    pub duration_seconds: i32,
+    pub score: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Runs are only comparable when played on the same levels, content and clock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeAttackKey {
+    pub level_set: String,
+    pub content_mode: String,
+    pub duration_seconds: i32,
+}
+
+impl TimeAttackKey {
+    pub fn new(levels: &[shared::DifficultySet], content_mode: &shared::ContentMode, duration_seconds: u32) -> Self {
+        let mut levels = levels.to_vec();
+        levels.sort();
+        levels.dedup();
+        Self {
+            level_set: levels.iter().map(ToString::to_string).collect::<Vec<_>>().join(","),
+            content_mode: format!("{:?}", content_mode),
+            duration_seconds: duration_seconds as i32,
+        }
+    }
+}
+
+impl TimeAttackRun {
+    /// Record a finished run
+    pub async fn create(pool: &DbPool, user_id: Uuid, key: &TimeAttackKey, score: i32) -> Result<Self, sqlx::Error> {
+        sqlx::query_as::<_, TimeAttackRun>(
+            r#"
+            INSERT INTO time_attack_runs (user_id, level_set, content_mode, duration_seconds, score)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, user_id, level_set, content_mode, duration_seconds, score, created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(&key.level_set)
+        .bind(&key.content_mode)
+        .bind(key.duration_seconds)
+        .bind(score)
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Highest score the user has reached for this key, if they have played it before
+    pub async fn personal_best(pool: &DbPool, user_id: Uuid, key: &TimeAttackKey) -> Result<Option<i32>, sqlx::Error> {
+        sqlx::query_scalar::<_, Option<i32>>(
+            r#"
+            SELECT MAX(score)
+            FROM time_attack_runs
+            WHERE user_id = $1 AND level_set = $2 AND content_mode = $3 AND duration_seconds = $4
+            "#,
+        )
+        .bind(user_id)
+        .bind(&key.level_set)
+        .bind(&key.content_mode)
+        .bind(key.duration_seconds)
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,97 @@
+use shared::{ActivePrompt, PlayerId};
+use std::sync::Arc;
+use crate::{
+    error::AppError,
+    lobby::LobbyState,
+    models::{ReviewItem, User},
+    state::AppState,
+    types::Result,
+};
+
+/// Most due prompts loaded into one review session; anything past this waits for the next one.
+const REVIEW_SESSION_SIZE: i64 = 50;
+
+/// SM-2 grades for the two outcomes the game can see.
+const QUALITY_CORRECT: u32 = 4;
+const QUALITY_MISSED: u32 = 1;
+
+/// Where a prompt sits in a user's SM-2 review schedule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Schedule {
+    pub ease_factor: f64,
+    pub interval_days: u32,
+    pub repetitions: u32,
+    pub lapses: u32,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self { ease_factor: 2.5, interval_days: 0, repetitions: 0, lapses: 0 }
+    }
+}
+
+impl Schedule {
+    /// The schedule after answering the prompt (`correct`) or missing or skipping it.
+    /// A miss starts the prompt over at a one-day interval; each correct answer in a row spaces it out further.
+    pub fn review(self, correct: bool) -> Self {
+        let quality = if correct { QUALITY_CORRECT } else { QUALITY_MISSED };
+        let q = (5 - quality) as f64;
+        let ease_factor = (self.ease_factor + 0.1 - q * (0.08 + q * 0.02)).max(1.3);
+
+        if quality < 3 {
+            return Self { ease_factor, interval_days: 1, repetitions: 0, lapses: self.lapses + 1 };
+        }
+
+        let interval_days = match self.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (self.interval_days as f64 * self.ease_factor).round() as u32,
+        };
+        Self { ease_factor, interval_days, repetitions: self.repetitions + 1, lapses: self.lapses }
+    }
+}
+
+/// What a review item is keyed on: the mode and what it asks about, without the accepted answers.
+/// Answer lists aren't built in a stable order, and the same prompt should stay one item as they change.
+pub fn prompt_key(prompt: &ActivePrompt) -> String {
+    match prompt {
+        ActivePrompt::Kanji { character } => format!("Kanji:{}", character),
+        ActivePrompt::Compound { characters } => {
+            let mut characters = characters.clone();
+            characters.sort();
+            format!("Compound:{}", characters.concat())
+        },
+        ActivePrompt::KanjiReading { character, .. } => format!("KanjiReading:{}", character),
+        ActivePrompt::Vocab { word, .. } => format!("Vocab:{}", word),
+        ActivePrompt::ReverseVocab { reading, .. } => format!("ReverseVocab:{}", reading),
+        ActivePrompt::Meaning { word, .. } => format!("Meaning:{}", word),
+        ActivePrompt::Shiritori { word, .. } => format!("Shiritori:{}", word),
+    }
+}
+
+impl AppState {
+    /// Loads the leader's due reviews into the lobby and starts a Review game.
+    /// New prompts take over once the due ones run out.
+    pub async fn start_review(&self, lobby: &Arc<LobbyState>, player_id: &PlayerId) -> Result<()> {
+        if !lobby.is_leader(player_id) {
+            return Err(AppError::AuthError("Only lobby leader can start the game".to_string()));
+        }
+        lobby.check_solo_players(shared::GameMode::Review)?;
+
+        let pool = self.db_pool.read().await.clone()
+            .ok_or_else(|| AppError::InternalError("Database not configured".to_string()))?;
+        // Names aren't authenticated, so the queue comes from the account the leader's socket logged in as
+        let user = match lobby.get_player_account(player_id) {
+            Some(user_id) => User::find_by_id(&pool, user_id).await?,
+            None => None,
+        };
+        let user = match user {
+            Some(user) if !user.is_guest => user,
+            _ => return Err(AppError::AuthError("Log in to an account to use review mode".to_string())),
+        };
+
+        let due = ReviewItem::due(&pool, user.id, REVIEW_SESSION_SIZE).await?;
+        lobby.prompt_queue.write(|q| *q = due.into_iter().map(|item| item.prompt.0).collect());
+        lobby.start_game(player_id)
+    }
+}
@@ -0,0 +1,130 @@
+/// Romaji syllables and the hiragana they stand for, covering both Hepburn and Kunrei-shiki spellings.
+/// Lookups try the longest spelling first, so `sha` wins over `s` + `ha`.
+const SYLLABLES: &[(&str, &str)] = &[
+    // Small kana typed on their own
+    ("xtsu", "っ"), ("ltsu", "っ"), ("xtu", "っ"), ("ltu", "っ"),
+    ("xya", "ゃ"), ("xyu", "ゅ"), ("xyo", "ょ"), ("lya", "ゃ"), ("lyu", "ゅ"), ("lyo", "ょ"),
+    ("xa", "ぁ"), ("xi", "ぃ"), ("xu", "ぅ"), ("xe", "ぇ"), ("xo", "ぉ"),
+    ("la", "ぁ"), ("li", "ぃ"), ("lu", "ぅ"), ("le", "ぇ"), ("lo", "ぉ"),
+    ("xwa", "ゎ"),
+
+    // Hepburn
+    ("shi", "し"), ("chi", "ち"), ("tsu", "つ"), ("fu", "ふ"), ("ji", "じ"),
+    ("sha", "しゃ"), ("shu", "しゅ"), ("she", "しぇ"), ("sho", "しょ"),
+    ("cha", "ちゃ"), ("chu", "ちゅ"), ("che", "ちぇ"), ("cho", "ちょ"),
+    ("ja", "じゃ"), ("ju", "じゅ"), ("je", "じぇ"), ("jo", "じょ"),
+    ("jya", "じゃ"), ("jyu", "じゅ"), ("jyo", "じょ"),
+    ("fa", "ふぁ"), ("fi", "ふぃ"), ("fe", "ふぇ"), ("fo", "ふぉ"),
+    ("ti", "ち"), ("tu", "つ"),
+    ("thi", "てぃ"), ("dhi", "でぃ"), ("tsa", "つぁ"),
+
+    // Kunrei-shiki
+    ("si", "し"), ("zi", "じ"), ("hu", "ふ"), ("di", "ぢ"), ("du", "づ"),
+    ("sya", "しゃ"), ("syu", "しゅ"), ("syo", "しょ"),
+    ("tya", "ちゃ"), ("tyu", "ちゅ"), ("tyo", "ちょ"),
+    ("zya", "じゃ"), ("zyu", "じゅ"), ("zyo", "じょ"),
+    ("dya", "ぢゃ"), ("dyu", "ぢゅ"), ("dyo", "ぢょ"),
+
+    ("a", "あ"), ("i", "い"), ("u", "う"), ("e", "え"), ("o", "お"),
+    ("ka", "か"), ("ki", "き"), ("ku", "く"), ("ke", "け"), ("ko", "こ"),
+    ("ga", "が"), ("gi", "ぎ"), ("gu", "ぐ"), ("ge", "げ"), ("go", "ご"),
+    ("sa", "さ"), ("su", "す"), ("se", "せ"), ("so", "そ"),
+    ("za", "ざ"), ("zu", "ず"), ("ze", "ぜ"), ("zo", "ぞ"),
+    ("ta", "た"), ("te", "て"), ("to", "と"),
+    ("da", "だ"), ("de", "で"), ("do", "ど"),
+    ("na", "な"), ("ni", "に"), ("nu", "ぬ"), ("ne", "ね"), ("no", "の"),
+    ("ha", "は"), ("hi", "ひ"), ("he", "へ"), ("ho", "ほ"),
+    ("ba", "ば"), ("bi", "び"), ("bu", "ぶ"), ("be", "べ"), ("bo", "ぼ"),
+    ("pa", "ぱ"), ("pi", "ぴ"), ("pu", "ぷ"), ("pe", "ぺ"), ("po", "ぽ"),
+    ("ma", "ま"), ("mi", "み"), ("mu", "む"), ("me", "め"), ("mo", "も"),
+    ("ya", "や"), ("yu", "ゆ"), ("yo", "よ"),
+    ("ra", "ら"), ("ri", "り"), ("ru", "る"), ("re", "れ"), ("ro", "ろ"),
+    ("wa", "わ"), ("wo", "を"), ("wi", "うぃ"), ("we", "うぇ"),
+    ("va", "ゔぁ"), ("vi", "ゔぃ"), ("vu", "ゔ"), ("ve", "ゔぇ"), ("vo", "ゔぉ"),
+
+    ("kya", "きゃ"), ("kyu", "きゅ"), ("kyo", "きょ"),
+    ("gya", "ぎゃ"), ("gyu", "ぎゅ"), ("gyo", "ぎょ"),
+    ("nya", "にゃ"), ("nyu", "にゅ"), ("nyo", "にょ"),
+    ("hya", "ひゃ"), ("hyu", "ひゅ"), ("hyo", "ひょ"),
+    ("bya", "びゃ"), ("byu", "びゅ"), ("byo", "びょ"),
+    ("pya", "ぴゃ"), ("pyu", "ぴゅ"), ("pyo", "ぴょ"),
+    ("mya", "みゃ"), ("myu", "みゅ"), ("myo", "みょ"),
+    ("rya", "りゃ"), ("ryu", "りゅ"), ("ryo", "りょ"),
+
+    ("-", "ー"),
+];
+
+/// Longest romaji spelling in `SYLLABLES`.
+const MAX_SYLLABLE_LEN: usize = 4;
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o')
+}
+
+/// Spells out the Hepburn macron and Kunrei circumflex long vowels, e.g. `tōkyō` as `toukyou`.
+fn expand_long_vowels(input: &str) -> String {
+    input.chars()
+        .flat_map(|c| match c {
+            'ā' | 'â' => vec!['a', 'a'],
+            'ī' | 'î' => vec!['i', 'i'],
+            'ū' | 'û' => vec!['u', 'u'],
+            'ē' | 'ê' => vec!['e', 'e'],
+            'ō' | 'ô' => vec!['o', 'u'],
+            c => vec![c],
+        })
+        .collect()
+}
+
+/// Converts a romaji answer to hiragana, or `None` if any part of it isn't romaji.
+/// Doubled consonants (`kko`, `tch`) become っ; `n` becomes ん before a consonant, at the end, as `nn` or as `n'`.
+pub fn romaji_to_kana(input: &str) -> Option<String> {
+    let romaji: Vec<char> = expand_long_vowels(&input.to_lowercase()).chars().collect();
+    if romaji.is_empty() {
+        return None;
+    }
+
+    let mut kana = String::new();
+    let mut i = 0;
+    while i < romaji.len() {
+        let c = romaji[i];
+        let next = romaji.get(i + 1).copied();
+
+        if c == 'n' {
+            match next {
+                Some('\'') => { kana.push('ん'); i += 2; continue; },
+                // A second n either starts the next syllable or is part of an IME-style "nn"
+                Some('n') => {
+                    let starts_syllable = romaji.get(i + 2).is_some_and(|&c| is_vowel(c) || c == 'y');
+                    kana.push('ん');
+                    i += if starts_syllable { 1 } else { 2 };
+                    continue;
+                },
+                Some(n) if is_vowel(n) || n == 'y' => {},
+                _ => { kana.push('ん'); i += 1; continue; },
+            }
+        }
+
+        // Traditional Hepburn writes ん as m before labials, as in "shimbun"
+        if c == 'm' && matches!(next, Some('b' | 'p')) {
+            kana.push('ん');
+            i += 1;
+            continue;
+        }
+
+        let doubled = next == Some(c) && c.is_ascii_alphabetic() && !is_vowel(c);
+        if doubled || (c == 't' && next == Some('c') && romaji.get(i + 2) == Some(&'h')) {
+            kana.push('っ');
+            i += 1;
+            continue;
+        }
+
+        let (len, syllable) = (1..=MAX_SYLLABLE_LEN.min(romaji.len() - i)).rev()
+            .find_map(|len| {
+                let spelling: String = romaji[i..i + len].iter().collect();
+                SYLLABLES.iter().find(|(r, _)| *r == spelling).map(|(_, k)| (len, *k))
+            })?;
+        kana.push_str(syllable);
+        i += len;
+    }
+    Some(kana)
+}
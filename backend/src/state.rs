@@ -1,11 +1,13 @@
-use shared::LobbyId;
+use shared::{DifficultySet, LobbyId};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     env,
+    path::Path,
     sync::Arc,
 };
 use crate::{
-    data::{vectorize_joyo_kanji, load_dictionary, load_jlpt_words, JlptWordData, KanjiData, DictData},
+    data::{vectorize_joyo_kanji, load_joyo_kanji, load_kyoiku_kanji, load_jlpt_words, load_kanjidic, load_kanjidic2, split_unplayable_kanji, load_word_ranks, rank_jlpt_words, load_denylist, WordFilter, WordIndex, DEFAULT_FILTER_TAGS, attach_kanji_readings, attach_kanji_details, index_readings, index_spellings, GameData},
+    dictionary::{CsvDictionary, Dictionary, JmdictDictionary},
     db::DbPool,
     error::AppError,
     types::{Result, Shared},
@@ -16,27 +18,66 @@ pub use shared::{
 };
 
 
+/// Kanji found in fewer dictionary words than this aren't prompted, unless `MIN_KANJI_WORDS` says otherwise
+const DEFAULT_MIN_KANJI_WORDS: usize = 3;
+
 pub struct AppState {
     pub lobbies: Shared<HashMap<LobbyId, Arc<LobbyState>>>,
     pub db_pool: tokio::sync::RwLock<Option<Arc<DbPool>>>,
-    pub kanji_data: Arc<KanjiData>,
-    pub word_data: Arc<JlptWordData>,
-    pub dict_data: Arc<DictData>,
+    pub game_data: GameData,
 }
 
 impl AppState {
 
-    fn load_data() -> Result<(Arc<KanjiData>, Arc<JlptWordData>, Arc<DictData>)> {
+    fn data_dir() -> &'static str {
         let is_production = matches!(
             env::var("PRODUCTION").as_deref(),
             Ok("1") | Ok("true") | Ok("yes")
         );
 
-        let data_dir = if is_production {
+        if is_production {
             "/usr/local/data"
         } else {
             "../data"
+        }
+    }
+
+    /// The quality filter for dictionary words. `DICT_FILTER_TAGS` replaces the default tag list (comma-separated, empty for none),
+    /// `DICT_FILTER_JAPANESE_ONLY=0` turns off the character check, and `word_denylist.txt` in the data directory lists words to catch.
+    fn word_filter(data_dir: &str) -> WordFilter {
+        let tags = match env::var("DICT_FILTER_TAGS") {
+            Ok(tags) => tags.split(',').map(str::trim).filter(|t| !t.is_empty()).map(str::to_string).collect(),
+            Err(_) => DEFAULT_FILTER_TAGS.iter().map(|t| t.to_string()).collect(),
         };
+        let japanese_only = !matches!(
+            env::var("DICT_FILTER_JAPANESE_ONLY").as_deref(),
+            Ok("0") | Ok("false") | Ok("no")
+        );
+        // The denylist is optional: without it only the character and tag rules apply
+        let denylist_path = format!("{}/word_denylist.txt", data_dir);
+        let denylist = load_denylist(&denylist_path).unwrap_or_else(|e| {
+            tracing::warn!("No word denylist loaded from {}: {}", denylist_path, e);
+            HashSet::new()
+        });
+        WordFilter { japanese_only, tags, denylist }
+    }
+
+    /// JMdict XML when `JMDICT_PATH` names a file or `JMdict_e.xml` is in the data directory, otherwise the bundled CSVs.
+    fn dictionary_source(data_dir: &str) -> Box<dyn Dictionary> {
+        let filter = Self::word_filter(data_dir);
+        let jmdict_path = env::var("JMDICT_PATH").unwrap_or_else(|_| format!("{}/JMdict_e.xml", data_dir));
+        if Path::new(&jmdict_path).is_file() {
+            return Box::new(JmdictDictionary { path: jmdict_path.into(), filter });
+        }
+        Box::new(CsvDictionary {
+            words_path: format!("{}/kanji_words.csv", data_dir),
+            glosses_path: format!("{}/jmdict_glosses.csv", data_dir),
+            filter,
+        })
+    }
+
+    fn load_data() -> Result<GameData> {
+        let data_dir = Self::data_dir();
 
         let kanji_list_paths: Vec<String> = vec![
             format!("{}/N1_kanji.csv", data_dir),
@@ -52,24 +93,95 @@ impl AppState {
             format!("{}/N4_words.csv", data_dir),
             format!("{}/N5_words.csv", data_dir),
         ];
-        let dictionary_path = format!("{}/kanji_words.csv", data_dir);
 
+        let mut list_of_kanji = vectorize_joyo_kanji(&kanji_list_paths)?;
+        let joyo = load_joyo_kanji(&format!("{}/joyo_kanji.csv", data_dir), &list_of_kanji)?;
+        let joyo_idx = list_of_kanji.len();
+        list_of_kanji.push(joyo);
 
-        let list_of_kanji = Arc::new(vectorize_joyo_kanji(&kanji_list_paths)?);
+        // Readings are optional: without them only the kanji-reading quiz is unavailable.
+        // KANJIDIC2 also brings meanings, stroke counts, grades and radicals; the older text format only readings.
+        let kanjidic2_path = format!("{}/kanjidic2.xml", data_dir);
+        let kanjidic_path = format!("{}/kanjidic.txt", data_dir);
+        match load_kanjidic2(&kanjidic2_path) {
+            Ok(kanjidic) => attach_kanji_details(&mut list_of_kanji, &kanjidic),
+            Err(e) => {
+                tracing::warn!("No kanji details loaded from {}: {}", kanjidic2_path, e);
+                match load_kanjidic(&kanjidic_path) {
+                    Ok(kanjidic) => attach_kanji_readings(&mut list_of_kanji, &kanjidic),
+                    Err(e) => tracing::warn!("No kanji readings loaded from {}: {}", kanjidic_path, e),
+                }
+            },
+        }
+        // Grade sets are copied from the Jōyō set once it has its readings and details
+        let grades = load_kyoiku_kanji(&format!("{}/kyoiku_kanji.csv", data_dir), &list_of_kanji[joyo_idx])?;
+        list_of_kanji.extend(grades);
         let list_of_words = Arc::new(load_jlpt_words(&word_list_paths)?);
-        let dictionary_list = Arc::new(load_dictionary(&dictionary_path)?);
 
-        Ok((list_of_kanji, list_of_words, dictionary_list))
+        let source = Self::dictionary_source(data_dir);
+        tracing::info!("Loading dictionary from {}", source.describe());
+        let mut dictionary = source.load()?;
+        tracing::info!(
+            "{} of {} dictionary words are filtered out of common-words lobbies",
+            dictionary.words.iter().filter(|e| e.filtered).count(),
+            dictionary.words.len(),
+        );
+        if !dictionary.words.iter().any(|e| !e.tags.is_empty()) {
+            tracing::warn!("The dictionary has no word tags, so only the character check and the denylist filter words; tag filtering needs JMdict");
+        }
+        dictionary.words.attach_word_levels(&list_of_words);
+
+        // Ranks are optional: without them weighted Vocab games fall back to the dictionary's ranks
+        let ranks_path = format!("{}/word_frequency.csv", data_dir);
+        let ranks = load_word_ranks(&ranks_path).unwrap_or_else(|e| {
+            tracing::warn!("No word frequency ranks loaded from {}: {}", ranks_path, e);
+            HashMap::new()
+        });
+        let mut word_ranks = rank_jlpt_words(&list_of_words, &ranks, &dictionary.words);
+        // With no frequencies at all, weight by the JLPT study order from scripts/jlpt_word_order.py instead.
+        // It's only used as a whole, so it's never mixed with real ranks on a different scale.
+        if word_ranks.is_empty() {
+            let order_path = format!("{}/jlpt_word_order.csv", data_dir);
+            match load_word_ranks(&order_path) {
+                Ok(order) => word_ranks = rank_jlpt_words(&list_of_words, &order, &WordIndex::default()),
+                Err(e) => tracing::warn!("No JLPT word order loaded from {}: {}", order_path, e),
+            }
+        }
+
+        let min_words = env::var("MIN_KANJI_WORDS").ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MIN_KANJI_WORDS);
+        let hard_kanji = split_unplayable_kanji(&mut list_of_kanji, &dictionary.words, min_words);
+        for (idx, (playable, hard)) in list_of_kanji.iter().zip(&hard_kanji).enumerate() {
+            tracing::info!(
+                "{}: {} of {} kanji have at least {} words{}",
+                DifficultySet::ALL.get(idx).map_or_else(|| idx.to_string(), |s| s.label()),
+                playable.len(),
+                playable.len() + hard.len(),
+                min_words,
+                if hard.is_empty() { String::new() } else {
+                    format!("; hard pool: {}", hard.iter().map(|k| k.kanji.as_str()).collect::<String>())
+                },
+            );
+        }
+
+        Ok(GameData {
+            readings: Arc::new(index_readings(&list_of_words)),
+            spellings: Arc::new(index_spellings(&list_of_words)),
+            glosses: Arc::new(dictionary.glosses),
+            word_ranks: Arc::new(word_ranks),
+            kanji: Arc::new(list_of_kanji),
+            hard_kanji: Arc::new(hard_kanji),
+            words: list_of_words,
+            dictionary: Arc::new(dictionary.words),
+        })
     }
 
     pub fn create() -> Result<Self> {
-        let (kanji_data, word_data, dict_data) = Self::load_data()?;
         Ok(Self {
             lobbies: Shared::new(HashMap::new()),
             db_pool: tokio::sync::RwLock::new(None),
-            kanji_data,
-            word_data,
-            dict_data
+            game_data: Self::load_data()?,
         })
     }
 
@@ -0,0 +1,283 @@
+use shared::{Bracket, BracketEntrant, BracketMatch, GameStatus, LobbyId, PlayerId, ServerMessage};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+};
+use tokio::sync::broadcast;
+use crate::{
+    data::GameData,
+    db::DbPool,
+    error::AppError,
+    lobby::LobbyState,
+    state::AppState,
+    types::{Result, Shared},
+    utils::generate_lobby_id,
+};
+
+/// Lays `entrants` out as a single-elimination bracket in the order given.
+/// Byes fill out the first round to a power of two and their opponents advance straight away.
+pub fn seed_bracket(tournament_id: LobbyId, entrants: Vec<BracketEntrant>) -> Bracket {
+    let size = entrants.len().next_power_of_two().max(2);
+    let byes = size - entrants.len();
+
+    // Each bye takes the second slot of one of the first matches, so no match is empty
+    let mut entrants = entrants.into_iter();
+    let first_round: Vec<BracketMatch> = (0..size / 2)
+        .map(|idx| BracketMatch {
+            players: [entrants.next(), if idx < byes { None } else { entrants.next() }],
+            ..Default::default()
+        })
+        .collect();
+
+    let mut rounds = vec![first_round];
+    while rounds.last().is_some_and(|r| r.len() > 1) {
+        let matches = rounds.last().map_or(0, |r| r.len() / 2);
+        rounds.push(vec![BracketMatch::default(); matches]);
+    }
+
+    let mut bracket = Bracket { tournament_id, rounds, cancelled: false };
+    for idx in 0..byes {
+        if let Some(entrant) = bracket.rounds[0][idx].players[0].clone() {
+            record_winner(&mut bracket, 0, idx, entrant.id);
+        }
+    }
+    bracket
+}
+
+/// Marks `winner` as the winner of a match and moves them into their slot in the next round.
+pub fn record_winner(bracket: &mut Bracket, round: usize, idx: usize, winner: PlayerId) {
+    let Some(entrant) = bracket.rounds[round][idx].players.iter().flatten().find(|e| e.id == winner).cloned() else {
+        return;
+    };
+    bracket.rounds[round][idx].winner = Some(winner);
+
+    if let Some(next) = bracket.rounds.get_mut(round + 1) {
+        next[idx / 2].players[idx % 2] = Some(entrant);
+    }
+}
+
+/// Matches with both players decided that haven't been given a lobby yet.
+fn ready_matches(bracket: &Bracket) -> Vec<(usize, usize, [BracketEntrant; 2])> {
+    bracket.rounds.iter().enumerate()
+        .flat_map(|(round, matches)| matches.iter().enumerate().map(move |(idx, m)| (round, idx, m)))
+        .filter(|(_, _, m)| m.lobby_id.is_none() && m.winner.is_none())
+        .filter_map(|(round, idx, m)| match &m.players {
+            [Some(a), Some(b)] => Some((round, idx, [a.clone(), b.clone()])),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Who a match lobby has produced as winner, if it is decided.
+/// A player who leaves mid-match hands the win to their opponent.
+fn match_outcome(lobby: &LobbyState, entrants: &[BracketEntrant; 2]) -> Option<PlayerId> {
+    let players = lobby.get_all_players();
+    let present: Vec<&BracketEntrant> = entrants.iter()
+        .filter(|e| players.iter().any(|p| p.id == e.id))
+        .collect();
+
+    if let [only] = present.as_slice() {
+        return Some(only.id.clone());
+    }
+    if present.is_empty() || lobby.game_status.read(|s| *s) != GameStatus::Finished {
+        return None;
+    }
+
+    players.iter()
+        .filter(|p| entrants.iter().any(|e| e.id == p.id))
+        .max_by_key(|p| (!p.is_eliminated, p.score))
+        .map(|p| p.id.clone())
+}
+
+/// Drives one tournament: creates match lobbies as pairings are decided and advances winners.
+#[derive(Clone)]
+struct Tournament {
+    tournament_id: LobbyId,
+    host: Arc<LobbyState>,
+    lobbies: Shared<HashMap<LobbyId, Arc<LobbyState>>>,
+    game_data: GameData,
+    db_pool: Option<Arc<DbPool>>,
+}
+
+impl Tournament {
+    /// Send `msg` to the tournament lobby and every match lobby still open.
+    fn broadcast(&self, msg: ServerMessage) {
+        self.host.broadcast(msg.clone());
+
+        let match_ids: Vec<LobbyId> = self.host.bracket.read(|b| {
+            b.iter().flat_map(|b| b.rounds.iter().flatten()).filter_map(|m| m.lobby_id.clone()).collect()
+        });
+        let match_lobbies: Vec<Arc<LobbyState>> = self.lobbies.read(|lobbies| {
+            match_ids.iter().filter_map(|id| lobbies.get(id).cloned()).collect()
+        });
+        for lobby in match_lobbies {
+            lobby.broadcast(msg.clone());
+        }
+    }
+
+    fn broadcast_bracket(&self) {
+        if let Some(bracket) = self.host.bracket.read(|b| b.clone()) {
+            self.broadcast(ServerMessage::BracketUpdate { bracket });
+        }
+    }
+
+    /// Open a lobby for every match whose players are known and tell the players where to go.
+    fn start_ready_matches(&self) -> Result<()> {
+        let ready = self.host.bracket.read(|b| b.as_ref().map(ready_matches).unwrap_or_default());
+
+        let mut started = Vec::new();
+        for (round, idx, entrants) in ready {
+            let lobby_id = self.start_match(round, idx, &entrants)?;
+            started.push((lobby_id, entrants));
+        }
+
+        self.broadcast_bracket();
+        for (lobby_id, entrants) in started {
+            self.broadcast(ServerMessage::TournamentMatchReady {
+                lobby_id,
+                player_ids: entrants.iter().map(|e| e.id.clone()).collect(),
+            });
+        }
+        Ok(())
+    }
+
+    fn start_match(&self, round: usize, idx: usize, entrants: &[BracketEntrant; 2]) -> Result<LobbyId> {
+        let lobby_id = generate_lobby_id();
+
+        let mut lobby = LobbyState::new(&self.game_data, None, self.db_pool.clone());
+        lobby.bracket = self.host.bracket.clone();
+        let lobby = Arc::new(lobby);
+
+        for entrant in entrants {
+            let bot = self.host.players.read(|ps| ps.iter().find(|p| p.id == entrant.id).and_then(|p| p.bot));
+            lobby.insert_player(entrant.id.clone(), entrant.name.clone(), bot)?;
+            if let Some(profile) = bot {
+                crate::bot::spawn_bot(&lobby, entrant.id.clone(), profile);
+            }
+        }
+        // Bots can't lead, so when two meet someone still has to own the lobby
+        lobby.lobby_leader.write(|l| *l = entrants[0].id.clone());
+        let settings = self.host.settings.read(|s| shared::GameSettings {
+            mode: shared::GameMode::Duel,
+            max_players: 2,
+            is_public: false,
+            teams: Vec::new(),
+            // The bracket only looks at the first game, so matches are never a series
+            series_length: None,
+            ..s.clone()
+        });
+        lobby.settings.write(|s| *s = settings);
+
+        // Subscribe before starting so the end of the match can't be missed
+        let rx = lobby.tx.subscribe();
+        // Only publish the lobby once it's running, so a failed start leaves no slot pointing at it
+        lobby.start_game(&entrants[0].id)?;
+
+        self.host.bracket.write(|b| {
+            if let Some(b) = b {
+                b.rounds[round][idx].lobby_id = Some(lobby_id.clone());
+            }
+        });
+        self.lobbies.write(|lobbies| { lobbies.insert(lobby_id.clone(), Arc::clone(&lobby)); });
+
+        let tournament = self.clone();
+        let watched = Arc::downgrade(&lobby);
+        let entrants = entrants.clone();
+        tokio::spawn(async move {
+            tournament.watch_match(round, idx, entrants, watched, rx).await;
+        });
+
+        Ok(lobby_id)
+    }
+
+    /// Waits for a match lobby to produce a winner. If the lobby is closed first the tournament is cancelled.
+    async fn watch_match(&self, round: usize, idx: usize, entrants: [BracketEntrant; 2], lobby: Weak<LobbyState>, mut rx: broadcast::Receiver<String>) {
+        // Any message may follow a change worth checking; a lagged receiver just checks sooner
+        while let Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) = rx.recv().await {
+            let Some(lobby) = lobby.upgrade() else { break };
+            if let Some(winner) = match_outcome(&lobby, &entrants) {
+                self.finish_match(round, idx, winner);
+                return;
+            }
+        }
+
+        tracing::warn!("Tournament {} match {}-{} closed without a winner, cancelling", self.tournament_id.0, round, idx);
+        self.host.bracket.write(|b| {
+            if let Some(b) = b {
+                b.cancelled = true;
+            }
+        });
+        self.broadcast_bracket();
+        self.schedule_cleanup();
+    }
+
+    fn finish_match(&self, round: usize, idx: usize, winner: PlayerId) {
+        self.host.bracket.write(|b| {
+            if let Some(b) = b {
+                record_winner(b, round, idx, winner);
+            }
+        });
+
+        if let Err(e) = self.start_ready_matches() {
+            tracing::error!("Failed to start next tournament matches for {}: {:?}", self.tournament_id.0, e);
+        }
+        if !self.host.bracket.read(|b| b.as_ref().is_some_and(|b| b.is_running())) {
+            self.schedule_cleanup();
+        }
+    }
+
+    /// The lobby's usual cleanup is held off while the tournament runs, so check again once it's over.
+    fn schedule_cleanup(&self) {
+        let tournament = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            if tournament.host.all_disconnected() {
+                tracing::info!("Tournament lobby {} finished with all players disconnected, cleaning up", tournament.tournament_id.0);
+                let matches: Vec<LobbyId> = tournament.host.bracket.read(|b| {
+                    b.iter().flat_map(|b| b.rounds.iter().flatten()).filter_map(|m| m.lobby_id.clone()).collect()
+                });
+                tournament.lobbies.write(|lobbies| {
+                    lobbies.remove(&tournament.tournament_id);
+                    for id in &matches {
+                        lobbies.remove(id);
+                    }
+                });
+            }
+        });
+    }
+}
+
+impl AppState {
+    /// Pairs the lobby's players into a bracket and starts the first round of Duel matches.
+    pub async fn start_tournament(&self, lobby_id: &LobbyId, lobby: &Arc<LobbyState>, player_id: &PlayerId) -> Result<()> {
+        if !lobby.is_leader(player_id) {
+            return Err(AppError::AuthError("Only lobby leader can start the tournament".to_string()));
+        }
+        if lobby.bracket.read(|b| b.as_ref().is_some_and(|b| b.is_running())) {
+            return Err(AppError::InvalidInput("A tournament is already running".to_string()));
+        }
+
+        let mut entrants: Vec<BracketEntrant> = lobby.get_all_players().into_iter()
+            .filter(|p| !p.is_spectator)
+            .map(|p| BracketEntrant { id: p.id, name: p.name })
+            .collect();
+        if entrants.len() < 2 {
+            return Err(AppError::InvalidInput("A tournament needs at least 2 players".to_string()));
+        }
+        {
+            use rand::seq::SliceRandom;
+            entrants.shuffle(&mut rand::rng());
+        }
+
+        lobby.bracket.write(|b| *b = Some(seed_bracket(lobby_id.clone(), entrants)));
+
+        let tournament = Tournament {
+            tournament_id: lobby_id.clone(),
+            host: Arc::clone(lobby),
+            lobbies: self.lobbies.clone(),
+            game_data: self.game_data.clone(),
+            db_pool: self.db_pool.read().await.clone(),
+        };
+        tournament.start_ready_matches()
+    }
+}
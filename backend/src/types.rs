@@ -6,9 +6,15 @@ use shared::PlayerId;
 use chrono::{DateTime, Utc};
 
 // Newtype wrapper for Arc<RwLock<T>>
-#[derive(Clone)]
 pub struct Shared<T>(Arc<RwLock<T>>);
 
+// Cloning only shares the handle, so `T` itself doesn't need to be `Clone`
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
 impl<T> Shared<T> {
     /// Create a new shared value
     pub fn new(value: T) -> Self {
@@ -45,6 +51,10 @@ pub struct PlayerData {
     pub is_eliminated: bool,
     pub is_connected: bool,
     pub is_spectator: bool,
+    /// How the player is driven when it is a bot
+    pub bot: Option<shared::BotProfile>,
+    /// The account their socket's token was issued for; names aren't authenticated, so this is what records are kept under
+    pub user_id: Option<uuid::Uuid>,
 }
 
 
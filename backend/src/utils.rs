@@ -1,6 +1,7 @@
 use rand::{RngExt, distr::Alphanumeric};
-use shared::{ActivePrompt, LobbyId};
-use std::collections::HashSet;
+use shared::{ActivePrompt, AnswerStrictness, LobbyId, WordInfo};
+use unicode_normalization::UnicodeNormalization;
+use crate::{data::{DictData, DictEntry, ReadingData}, deinflect::dictionary_forms};
 pub use shared::PlayerId;
 
 
@@ -20,14 +21,162 @@ pub fn generate_lobby_id() -> LobbyId {
     LobbyId::from(generate_random_id(6))
 }
 
+/// Seed for a day's daily-challenge prompts, the same in every lobby on that (UTC) date.
+pub fn daily_challenge_seed(date: chrono::NaiveDate) -> u64 {
+    use chrono::Datelike;
+    date.num_days_from_ce() as u64
+}
+
 
-pub fn check_prompt(prompt: &shared::ActivePrompt, input: &str, dictionary: &HashSet<String>) -> bool {
+/// Checks `input` against `prompt`, returning what is known about the accepted word if it's correct.
+/// Words the dictionary's filters caught only count when `full_dictionary` is set.
+pub fn check_prompt(prompt: &shared::ActivePrompt, input: &str, dictionary: &DictData, readings: &ReadingData, strictness: AnswerStrictness, exact_headwords: bool, full_dictionary: bool) -> Option<WordInfo> {
+    let input = clean_answer(input, strictness);
+    let input = input.as_str();
+    // Inflected answers count when one of the forms they could come from is in the dictionary
+    let lookup = |word: &str| {
+        dictionary.lookup(word, full_dictionary).or_else(|| {
+            if exact_headwords { return None; }
+            dictionary_forms(word).iter().find_map(|w| dictionary.lookup(w, full_dictionary))
+        })
+    };
+    let describe = |word: &str| dictionary.describe(word, readings);
     match prompt {
         ActivePrompt::Kanji { character } => {
-            input.contains(character.as_str()) && dictionary.contains(input)
+            if !input.contains(character.as_str()) { return None; }
+            lookup(input).map(DictEntry::info)
+        },
+        ActivePrompt::Compound { characters } => {
+            if !characters.iter().all(|c| input.contains(c.as_str())) { return None; }
+            lookup(input).map(DictEntry::info)
+        },
+        ActivePrompt::Vocab { word, readings, .. } => {
+            let answer = reading_key(input, strictness);
+            readings.iter().any(|r| reading_key(r, strictness) == answer).then(|| describe(word))
+        },
+        ActivePrompt::ReverseVocab { words, .. } => {
+            words.iter().any(|w| w == input).then(|| describe(input))
         },
-        ActivePrompt::Vocab { readings, .. } => {
-            readings.iter().any(|r| r == input)
+        ActivePrompt::KanjiReading { character, readings } => {
+            let answer = reading_key(input, strictness);
+            readings.iter()
+                .map(|r| normalize_kanji_reading(r))
+                .find(|r| reading_key(r, strictness) == answer)
+                .map(|reading| WordInfo {
+                    word: character.clone(),
+                    readings: vec![reading],
+                    ..Default::default()
+                })
+        },
+        ActivePrompt::Meaning { word, readings, .. } => {
+            let answer = reading_key(input, strictness);
+            (word == input || readings.iter().any(|r| reading_key(r, strictness) == answer)).then(|| describe(word))
+        },
+        ActivePrompt::Shiritori { reading, .. } => {
+            if !full_dictionary && dictionary.get(input).is_some_and(|e| e.filtered) { return None; }
+            shiritori_reading(reading, input, readings).map(|_| describe(input))
         }
     }
 }
+
+/// Tidies up how an answer was typed before it is checked.
+/// Past `Strict`, full-width and half-width forms are folded together (NFKC) and surrounding punctuation is dropped.
+pub fn clean_answer(input: &str, strictness: AnswerStrictness) -> String {
+    if strictness == AnswerStrictness::Strict {
+        return input.trim().to_string();
+    }
+    input.nfkc()
+        .collect::<String>()
+        .trim_matches(|c: char| c.is_whitespace() || is_punctuation(c))
+        .to_string()
+}
+
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || matches!(c, '。' | '、' | '・' | '「' | '」' | '『' | '』' | '〜' | '…')
+}
+
+/// The form a kana reading is compared in under `strictness`.
+/// Katakana counts as hiragana past `Strict`, and `Lenient` also spells out each ー as the vowel before it.
+pub fn reading_key(reading: &str, strictness: AnswerStrictness) -> String {
+    match strictness {
+        AnswerStrictness::Strict => reading.to_string(),
+        AnswerStrictness::Standard => reading.chars().map(to_hiragana).collect(),
+        AnswerStrictness::Lenient => {
+            let mut key = String::with_capacity(reading.len());
+            for c in reading.chars().map(to_hiragana) {
+                let c = match c {
+                    'ー' => key.chars().last().and_then(kana_vowel).unwrap_or(c),
+                    _ => c,
+                };
+                key.push(c);
+            }
+            key
+        },
+    }
+}
+
+/// The vowel a hiragana syllable ends on, if it has one.
+fn kana_vowel(c: char) -> Option<char> {
+    const ROWS: [(&str, char); 5] = [
+        ("あかがさざただなはばぱまやらわぁゃゎ", 'あ'),
+        ("いきぎしじちぢにひびぴみりぃ", 'い'),
+        ("うくぐすずつづぬふぶぷむゆるぅゅっゔ", 'う'),
+        ("えけげせぜてでねへべぺめれぇ", 'え'),
+        ("おこごそぞとどのほぼぽもよろをぉょ", 'お'),
+    ];
+    ROWS.iter().find(|(row, _)| row.contains(c)).map(|(_, vowel)| *vowel)
+}
+
+/// Maps katakana to hiragana, leaving every other character untouched.
+pub fn to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Turns a KANJIDIC reading such as `ニチ` or `つ.ぐ` into the plain hiragana a player would type.
+pub fn normalize_kanji_reading(reading: &str) -> String {
+    reading.chars()
+        .filter(|c| *c != '.' && *c != '-')
+        .map(to_hiragana)
+        .collect()
+}
+
+pub fn is_kana(c: char) -> bool {
+    matches!(c, 'ぁ'..='ゖ' | 'ァ'..='ヺ' | 'ー')
+}
+
+/// CJK ideographs, including the rarer extension and compatibility blocks, plus the repetition mark 々.
+pub fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' | '\u{20000}'..='\u{2FA1F}' | '々')
+}
+
+/// The kana the next shiritori word has to start with.
+/// Trailing long-vowel marks are ignored and small kana count as their full-size form.
+pub fn shiritori_tail(reading: &str) -> Option<char> {
+    let last = reading.chars().rev().find(|&c| c != 'ー')?;
+    let c = match to_hiragana(last) {
+        'ぁ' => 'あ', 'ぃ' => 'い', 'ぅ' => 'う', 'ぇ' => 'え', 'ぉ' => 'お',
+        'っ' => 'つ', 'ゃ' => 'や', 'ゅ' => 'ゆ', 'ょ' => 'よ', 'ゎ' => 'わ',
+        other => other,
+    };
+    Some(c)
+}
+
+pub fn ends_with_n(reading: &str) -> bool {
+    reading.chars().last().map(to_hiragana) == Some('ん')
+}
+
+/// Returns the reading of `input` that continues the chain from `previous_reading`,
+/// or `None` if the word is unknown or none of its readings start with the right kana.
+pub fn shiritori_reading<'a>(previous_reading: &str, input: &str, readings: &'a ReadingData) -> Option<&'a str> {
+    let tail = shiritori_tail(previous_reading)?;
+    readings.get(input)?
+        .iter()
+        .filter(|r| r.chars().all(is_kana))
+        .filter(|r| r.chars().next().map(to_hiragana) == Some(tail))
+        // Prefer a reading that keeps the chain alive
+        .min_by_key(|r| ends_with_n(r))
+        .map(String::as_str)
+}
@@ -181,3 +181,43 @@ async fn test_get_kanji_after_game_start() {
     let res = state.get_prompt(lobby_id.clone()).await.unwrap();
     assert!(!res.prompt.is_empty());
 }
+
+#[tokio::test]
+async fn test_lookup_kanji_words_returns_matching_words() {
+    let state = get_state().await;
+    let words = state.lookup_kanji_words("日".into(), 5).await.unwrap();
+    assert!(!words.is_empty() && words.len() <= 5);
+    assert!(words.iter().all(|w| w.word.contains('日')));
+
+    assert!(state.lookup_kanji_words("日本".into(), 5).await.is_err());
+    assert!(state.lookup_kanji_words("に".into(), 5).await.is_err());
+}
+
+#[tokio::test]
+async fn test_joyo_set_is_loaded() {
+    let state = get_state().await;
+    let idx = shared::DifficultySet::Joyo.index().unwrap();
+    let data = &state.game_data;
+    assert_eq!(data.kanji.len(), shared::DifficultySet::ALL.len());
+    assert_eq!(data.kanji[idx].len() + data.hard_kanji[idx].len(), 2136);
+    assert!(data.kanji[idx].iter().any(|k| k.kanji == "亜"));
+    // Weighted games can draw every Jōyō kanji, including those on no JLPT list
+    assert!(data.kanji[idx].iter().all(|k| k.frequency > 0));
+
+    let grade_sizes: Vec<usize> = (1..=6)
+        .map(|g| shared::DifficultySet::Grade(g).index().unwrap())
+        .map(|i| data.kanji[i].len() + data.hard_kanji[i].len())
+        .collect();
+    assert_eq!(grade_sizes, vec![80, 160, 200, 202, 193, 191]);
+}
+
+#[tokio::test]
+async fn test_jlpt_words_are_weighted_without_frequencies() {
+    let state = get_state().await;
+    let data = &state.game_data;
+    // With no frequency list shipped, weighted Vocab games follow the JLPT study order
+    assert!(data.words.iter().flat_map(|level| level.keys()).all(|w| data.word_ranks.contains_key(w)));
+    assert!(data.word_ranks["一"] < data.word_ranks["馴れ馴れしい"]);
+    // The order is only for weighting; it never shows up as a dictionary frequency rank
+    assert!(data.dictionary.get("日本").is_some_and(|e| e.frequency_rank.is_none()));
+}
@@ -0,0 +1,49 @@
+use leptos::prelude::*;
+use crate::context::GameContext;
+
+/// Today's top daily-challenge scores, shown once a daily run is over.
+#[component]
+pub fn DailyLeaderboard() -> impl IntoView {
+    let game_context = use_context::<GameContext>().expect("GameContext missing");
+    let daily_result = game_context.daily_result;
+    let player_name = game_context.player_name;
+
+    // Refetch once our own run has been saved so it shows up in the list
+    let leaderboard = Resource::new(
+        move || daily_result.get(),
+        |_| async move { shared::get_daily_leaderboard().await.ok() }
+    );
+
+    view! {
+        <Transition fallback=move || view! { <p class="text-sm text-gray-500 italic">"Loading leaderboard..."</p> }>
+            {move || leaderboard.get().flatten().map(|board| view! {
+                <div class="text-left space-y-1">
+                    <p class="text-sm text-gray-500 dark:text-gray-400 uppercase tracking-wider font-semibold text-center">
+                        {format!("Leaderboard {}", board.date)}
+                    </p>
+                    {if board.entries.is_empty() {
+                        view! { <p class="text-sm text-gray-500 italic text-center">"No scores yet today."</p> }.into_any()
+                    } else {
+                        view! {
+                            <ol class="max-h-48 overflow-y-auto space-y-1">
+                                {board.entries.into_iter().map(|entry| {
+                                    let is_me = entry.username == player_name.get();
+                                    view! {
+                                        <li class=if is_me {
+                                            "flex justify-between px-3 py-1 rounded text-sm font-bold bg-blue-50 dark:bg-blue-900/40 text-gray-900 dark:text-white"
+                                        } else {
+                                            "flex justify-between px-3 py-1 rounded text-sm text-gray-700 dark:text-gray-200"
+                                        }>
+                                            <span>{format!("#{} {}", entry.rank, entry.username)}</span>
+                                            <span>{entry.score}</span>
+                                        </li>
+                                    }
+                                }).collect_view()}
+                            </ol>
+                        }.into_any()
+                    }}
+                </div>
+            })}
+        </Transition>
+    }
+}
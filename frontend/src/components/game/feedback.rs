@@ -8,12 +8,21 @@ pub fn GameFeedback() -> impl IntoView {
     
     let lobby_info = game_context.lobby_info;
     let result = game_context.result;
+    let last_check = game_context.last_check;
     let error_message = in_game_context.error_message;
 
     let content_mode = Signal::derive(move || {
         lobby_info.get().map(|i| i.settings.content_mode).unwrap_or_default()
     });
 
+    let game_mode = Signal::derive(move || {
+        lobby_info.get().map(|i| i.settings.mode).unwrap_or_default()
+    });
+
+    let accepted = Signal::derive(move || last_check.get().and_then(|c| c.accepted));
+    let suggestions = Signal::derive(move || last_check.get().and_then(|c| c.suggestions).unwrap_or_default());
+    let missed_kanji = Signal::derive(move || last_check.get().map(|c| c.missed_kanji).unwrap_or_default());
+
     let get_result_class = move || {
         let result_text = result.get();
         if result_text.is_empty() {
@@ -39,6 +48,36 @@ pub fn GameFeedback() -> impl IntoView {
                 <div class=get_result_class>{move || result.get()}</div>
             </Show>
 
+            // Dictionary details for the answer
+            <Show when=move || accepted.get().is_some()>
+                <p class="text-center text-sm text-gray-600 dark:text-gray-300">
+                    {move || accepted.get().map(|w| w.summary()).unwrap_or_default()}
+                </p>
+            </Show>
+            <Show when=move || !suggestions.get().is_empty()>
+                <div class="text-center text-sm text-gray-600 dark:text-gray-300">
+                    <p class="font-semibold">"You could have said:"</p>
+                    <ul>
+                        {move || suggestions.get().into_iter().map(|w| view! { <li>{w.summary()}</li> }).collect_view()}
+                    </ul>
+                </div>
+            </Show>
+            {move || missed_kanji.get().into_iter().filter(|k| k.has_details()).map(|k| {
+                let on = k.on_readings.join("、");
+                let kun = k.kun_readings.join("、");
+                view! {
+                    <div class="p-4 rounded-lg bg-gray-50 dark:bg-gray-700/50 text-sm text-gray-700 dark:text-gray-200 text-left space-y-1">
+                        <p>
+                            <span class="text-2xl kanji-font mr-2">{k.character.clone()}</span>
+                            <span class="font-semibold">{k.meanings.join(", ")}</span>
+                        </p>
+                        {(!on.is_empty()).then(|| view! { <p>"On: " {on}</p> })}
+                        {(!kun.is_empty()).then(|| view! { <p>"Kun: " {kun}</p> })}
+                        <p class="text-gray-500 dark:text-gray-400">{k.facts()}</p>
+                    </div>
+                }
+            }).collect_view()}
+
             // Error Message
             <Show when=move || !error_message.get().is_empty()>
                 <div class="p-4 rounded bg-red-100 dark:bg-red-900/30 text-red-700 dark:text-red-300 text-center font-medium">
@@ -50,10 +89,17 @@ pub fn GameFeedback() -> impl IntoView {
 
 
                 <p class="mb-2">{
-                        move || if content_mode.get() == shared::ContentMode::Vocab {
-                            "Enter the hiragana reading of the displayed word."
+                        move || if game_mode.get() == shared::GameMode::Shiritori {
+                            "Continue the chain: your word's reading must start with the last kana of the previous word. Words ending in ん knock you out!"
                         } else {
-                            "Type a Japanese word containing the displayed kanji."
+                            match content_mode.get() {
+                                shared::ContentMode::Kanji => "Type a Japanese word containing the displayed kanji.",
+                                shared::ContentMode::Compound => "Type a Japanese word containing both displayed kanji.",
+                                shared::ContentMode::KanjiReading => "Enter one of the displayed kanji's readings in hiragana.",
+                                shared::ContentMode::Vocab => "Enter the hiragana reading of the displayed word.",
+                                shared::ContentMode::ReverseVocab => "Write the displayed reading in kanji. Any spelling with that reading counts.",
+                                shared::ContentMode::Meaning => "Enter the Japanese word for the displayed English meaning.",
+                            }
                         }
                 }</p>
                 <p>"Click \"Submit\" to check your answer."</p>
@@ -1,5 +1,8 @@
 use leptos::prelude::*;
 use crate::context::{GameContext, InGameContext};
+use super::daily_leaderboard::DailyLeaderboard;
+use super::missed_words::MissedWordsPanel;
+use crate::components::lobby::{BracketView, SeriesScoreboard};
 use shared::GameMode;
 
 #[component]
@@ -12,6 +15,9 @@ pub fn GameOver() -> impl IntoView {
     let is_leader = game_context.is_leader;
     let on_return_to_lobby = in_game_context.on_return_to_lobby;
     let on_exit = in_game_context.on_exit_game;
+    let time_attack_result = game_context.time_attack_result;
+    let daily_result = game_context.daily_result;
+    let enter_lobby = game_context.enter_lobby;
 
     let players = Signal::derive(move || lobby_info.get().map(|i| i.players).unwrap_or_default());
     let mode = Signal::derive(move || lobby_info.get().map(|i| i.settings.mode).unwrap_or_default());
@@ -22,25 +28,37 @@ pub fn GameOver() -> impl IntoView {
         match mode.get() {
             GameMode::Deathmatch => {
                 let mut p = players_list.clone();
-                p.sort_by(|a, b| b.score.cmp(&a.score));
+                p.sort_by_key(|p| std::cmp::Reverse(p.score));
                 p.first().cloned()
             },
-            GameMode::Duel => {
+            GameMode::Duel | GameMode::Shiritori | GameMode::Survival | GameMode::Bomb => {
                 let mut active: Vec<_> = players_list.iter().filter(|p| !p.is_eliminated).cloned().collect();
                 if active.is_empty() {
                      let mut p = players_list.clone();
-                     p.sort_by(|a, b| b.score.cmp(&a.score));
+                     p.sort_by_key(|p| std::cmp::Reverse(p.score));
                      p.first().cloned()
                 } else {
-                    active.sort_by(|a, b| b.score.cmp(&a.score));
+                    active.sort_by_key(|p| std::cmp::Reverse(p.score));
                     active.first().cloned()
                 }
             },
-            GameMode::Zen => None,
+            GameMode::Zen | GameMode::TimeAttack | GameMode::Daily | GameMode::Tournament | GameMode::Review => None,
         }
     });
 
+    // In team Deathmatch the whole leading team wins
+    let winning_team = Signal::derive(move || {
+        if mode.get() != GameMode::Deathmatch {
+            return None;
+        }
+        shared::team_standings(&players.get()).into_iter().next()
+    });
+
     let is_winner = Signal::derive(move || {
+        if let Some((team, _)) = winning_team.get() {
+            let me = player_id.get();
+            return players.get().iter().any(|p| p.id == me && p.team.as_deref() == Some(team.as_str()));
+        }
         winner.get().map(|w| w.id == player_id.get()).unwrap_or(false)
     });
 
@@ -50,10 +68,10 @@ pub fn GameOver() -> impl IntoView {
 
                 <div class="mb-6">
                     <span class="text-6xl mb-4 block">
-                        {move || if is_winner.get() { "🏆" } else { "💀" }}
+                        {move || if is_winner.get() { "🏆" } else if mode.get().is_timed_run() { "⏱️" } else { "💀" }}
                     </span>
                     <h2 class="text-3xl font-bold bg-clip-text text-transparent bg-gradient-to-r from-blue-500 to-purple-600 dark:from-blue-400 dark:to-purple-500">
-                        {move || if is_winner.get() { "VICTORY!" } else { "GAME OVER" }}
+                        {move || if is_winner.get() { "VICTORY!" } else if mode.get().is_timed_run() { "TIME'S UP!" } else { "GAME OVER" }}
                     </h2>
                 </div>
 
@@ -61,21 +79,79 @@ pub fn GameOver() -> impl IntoView {
                     <p class="text-gray-600 dark:text-gray-300">
                         {move || match mode.get() {
                             GameMode::Deathmatch => "Target score reached!",
-                            GameMode::Duel => "Last player standing!",
+                            GameMode::Duel | GameMode::Shiritori | GameMode::Bomb => "Last player standing!",
                             GameMode::Zen => "Session Ended!",
+                            GameMode::Review => "Review session ended!",
+                            GameMode::TimeAttack => "The clock has run out!",
+                            GameMode::Survival => "Everyone is out of lives!",
+                            GameMode::Daily => "Today's challenge is over!",
+                            GameMode::Tournament => "The tournament is over!",
                         }}
                     </p>
 
-                    <div class="py-4 bg-gray-50 dark:bg-gray-700/50 rounded-lg">
+                    {move || lobby_info.get().and_then(|i| i.tier).map(|tier| view! {
+                        <p class="text-gray-600 dark:text-gray-300">"Reached level " <span class="font-semibold">{tier}</span></p>
+                    })}
+
+                    <Show when=move || mode.get().is_timed_run()>
+                        <div class="py-4 bg-gray-50 dark:bg-gray-700/50 rounded-lg space-y-1">
+                            <p class="text-sm text-gray-500 dark:text-gray-400 uppercase tracking-wider font-semibold">"Score"</p>
+                            <p class="text-4xl font-bold text-gray-900 dark:text-white">
+                                {move || players.get().into_iter().find(|p| p.id == player_id.get()).map(|p| p.score).unwrap_or_default()}
+                            </p>
+                            {move || time_attack_result.get().map(|r| view! {
+                                <p class="text-sm text-gray-600 dark:text-gray-300">
+                                    {if r.is_new_best { "New personal best!".to_string() } else { format!("Personal best: {}", r.personal_best) }}
+                                </p>
+                            })}
+                            {move || daily_result.get().map(|r| view! {
+                                <p class="text-sm text-gray-600 dark:text-gray-300">
+                                    {match r.rank {
+                                        Some(rank) if r.is_scored => format!("Rank #{} today", rank),
+                                        _ => "Practice run - only your first attempt each day is scored".to_string(),
+                                    }}
+                                </p>
+                            })}
+                        </div>
+                    </Show>
+
+                    <Show when=move || mode.get() == GameMode::Daily>
+                        <DailyLeaderboard />
+                    </Show>
+
+                    <SeriesScoreboard />
+
+                    <MissedWordsPanel />
+
+                    <div class="py-4 bg-gray-50 dark:bg-gray-700/50 rounded-lg" class:hidden=move || mode.get().is_timed_run()>
                         <p class="text-sm text-gray-500 dark:text-gray-400 uppercase tracking-wider font-semibold mb-1">
                             "Winner"
                         </p>
                         <p class="text-2xl font-bold text-gray-900 dark:text-white">
-                            {move || winner.get().map(|p| p.name).unwrap_or_else(|| "Unknown".to_string())}
+                            {move || match winning_team.get() {
+                                Some((team, total)) => format!("{} ({})", team, total),
+                                None => winner.get().map(|p| p.name).unwrap_or_else(|| "Unknown".to_string()),
+                            }}
                         </p>
                     </div>
                 </div>
 
+                // Tournament matches lead back to the bracket
+                {move || lobby_info.get().and_then(|i| i.bracket).map(|b| {
+                    let tournament_id = b.tournament_id.clone();
+                    view! {
+                        <div class="mb-6 space-y-3">
+                            <BracketView />
+                            <button
+                                on:click=move |_| enter_lobby.run(tournament_id.clone())
+                                class="w-full py-2 px-6 bg-gray-100 dark:bg-gray-700 hover:bg-gray-200 dark:hover:bg-gray-600 text-gray-800 dark:text-gray-100 rounded-lg font-semibold transition-colors"
+                            >
+                                "Back to Bracket"
+                            </button>
+                        </div>
+                    }
+                })}
+
                 <div class="space-y-3">
                     // Restart Lobby Button (Leader Only, Disabled for others)
                     <button
@@ -15,6 +15,10 @@ pub fn GameHeader() -> impl IntoView {
         lobby_info.get().map(|i| i.settings.content_mode).unwrap_or_default()
     });
 
+    let game_mode = Signal::derive(move || {
+        lobby_info.get().map(|i| i.settings.mode).unwrap_or_default()
+    });
+
     let player_name = Signal::derive(move || {
         lobby_info.get()
             .and_then(|info| info.players.into_iter().find(|p| p.id == player_id.get()))
@@ -40,13 +44,26 @@ pub fn GameHeader() -> impl IntoView {
         <div class="flex justify-between items-center mb-4 sm:mb-6 flex-wrap gap-2 sm:gap-4">
             <h2 class="text-xl sm:text-2xl font-bold text-gray-800 dark:text-gray-100">{
                 move || {
+                    if game_mode.get() == shared::GameMode::Shiritori {
+                        return "Shiritori";
+                    }
                     match content_mode.get() {
                         shared::ContentMode::Vocab => "Vocab",
                         shared::ContentMode::Kanji => "Kanji",
+                        shared::ContentMode::Compound => "Compound",
+                        shared::ContentMode::KanjiReading => "Readings",
+                        shared::ContentMode::ReverseVocab => "Spelling",
+                        shared::ContentMode::Meaning => "Meaning",
                     }
                 }
             }</h2>
             <div class="flex items-center gap-4 flex-wrap">
+                {move || lobby_info.get().and_then(|i| i.tier).map(|tier| view! {
+                    <div class="bg-purple-50 dark:bg-purple-900/30 px-3 py-1 rounded-full text-sm text-purple-700 dark:text-purple-300 whitespace-nowrap" title="Current survival level">
+                        "Level: "
+                        <span class="font-semibold ml-1">{tier}</span>
+                    </div>
+                })}
                 <div class="bg-blue-50 dark:bg-blue-900/30 px-3 py-1 rounded-full text-sm text-blue-700 dark:text-blue-300 flex items-center whitespace-nowrap">
                     "Player: "
                     <span class="font-semibold ml-1">{move || player_name.get()}</span>
@@ -43,6 +43,10 @@ pub fn GameInput() -> impl IntoView {
         lobby_info.get().map(|i| i.settings.content_mode).unwrap_or_default()
     });
 
+    let game_mode = Signal::derive(move || {
+        lobby_info.get().map(|i| i.settings.mode).unwrap_or_default()
+    });
+
     let is_spectator = Signal::derive(move || {
         lobby_info.get().map(|i| {
             i.players.iter().any(|p| p.id == player_id.get() && p.is_spectator)
@@ -54,7 +58,7 @@ pub fn GameInput() -> impl IntoView {
              return true;
          }
          if let Some(info) = lobby_info.get() {
-             if info.settings.mode == shared::GameMode::Duel {
+             if info.settings.mode.is_turn_based() {
                  let me = info.players.into_iter().find(|p| p.id == player_id.get());
                  !me.map(|p| p.is_turn).unwrap_or(false)
              } else {
@@ -125,10 +129,17 @@ pub fn GameInput() -> impl IntoView {
                 placeholder=move || {
                     if is_spectator.get() {
                         "Spectating match in progress..."
-                    } else if content_mode.get() == shared::ContentMode::Vocab {
-                        "Enter the reading in hiragana"
+                    } else if game_mode.get() == shared::GameMode::Shiritori {
+                        "Enter a word starting with the last kana"
                     } else {
-                        "Enter a Japanese word with this kanji"
+                        match content_mode.get() {
+                            shared::ContentMode::Kanji => "Enter a Japanese word with this kanji",
+                            shared::ContentMode::Compound => "Enter a Japanese word with both kanji",
+                            shared::ContentMode::KanjiReading => "Enter a reading of this kanji in hiragana",
+                            shared::ContentMode::Vocab => "Enter the reading in hiragana",
+                            shared::ContentMode::ReverseVocab => "Enter the word in kanji",
+                            shared::ContentMode::Meaning => "Enter the Japanese word in kanji or kana",
+                        }
                     }
                 }
                 disabled=move || is_loading.get() || disabled.get()
@@ -0,0 +1,67 @@
+use leptos::prelude::*;
+use crate::context::{GameContext, InGameContext};
+
+/// How many of the game's last kanji get suggestions
+const KANJI_SHOWN: usize = 5;
+/// Suggestions per kanji
+const WORDS_PER_KANJI: usize = 3;
+
+fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' | '々')
+}
+
+/// Common words for the kanji prompts of the game that just ended.
+#[component]
+pub fn MissedWordsPanel() -> impl IntoView {
+    let game_context = use_context::<GameContext>().expect("GameContext missing");
+    let in_game_context = use_context::<InGameContext>().expect("InGameContext missing");
+    let lobby_info = game_context.lobby_info;
+    let prompt_history = in_game_context.prompt_history;
+
+    let is_kanji_game = Signal::derive(move || lobby_info.get().is_some_and(|i| {
+        matches!(i.settings.content_mode, shared::ContentMode::Kanji | shared::ContentMode::Compound)
+    }));
+
+    let lookups = LocalResource::new(move || {
+        let mut kanji: Vec<char> = Vec::new();
+        for c in prompt_history.get().iter().rev().flat_map(|p| p.chars()).filter(|&c| is_kanji(c)) {
+            if kanji.len() == KANJI_SHOWN {
+                break;
+            }
+            if !kanji.contains(&c) {
+                kanji.push(c);
+            }
+        }
+        async move {
+            let mut found = Vec::new();
+            for c in kanji {
+                if let Ok(words) = shared::lookup_kanji_words(c.to_string(), WORDS_PER_KANJI).await {
+                    if !words.is_empty() {
+                        found.push((c, words));
+                    }
+                }
+            }
+            found
+        }
+    });
+
+    view! {
+        <Show when=move || is_kanji_game.get()>
+            <Transition fallback=|| ()>
+                {move || lookups.get().filter(|found| !found.is_empty()).map(|found| view! {
+                    <div class="py-3 px-4 bg-gray-50 dark:bg-gray-700/50 rounded-lg space-y-2 text-left">
+                        <p class="text-sm text-gray-500 dark:text-gray-400 uppercase tracking-wider font-semibold text-center">"Words You Could Have Used"</p>
+                        <ul class="max-h-48 overflow-y-auto space-y-1">
+                            {found.into_iter().map(|(kanji, words)| view! {
+                                <li class="text-sm text-gray-700 dark:text-gray-200">
+                                    <span class="font-bold mr-2">{kanji.to_string()}</span>
+                                    {words.iter().map(shared::WordInfo::summary).collect::<Vec<_>>().join(", ")}
+                                </li>
+                            }).collect_view()}
+                        </ul>
+                    </div>
+                })}
+            </Transition>
+        </Show>
+    }
+}
@@ -12,6 +12,8 @@ mod input;
 mod feedback;
 mod timer;
 mod game_over;
+mod daily_leaderboard;
+mod missed_words;
 
 use header::GameHeader;
 use prompt::PromptDisplay;
@@ -45,6 +47,7 @@ pub fn GameComponent(
     let is_loading = RwSignal::new(false);
     let error_message = RwSignal::new(String::new());
     let shake_trigger = RwSignal::new(false);
+    let prompt_history = RwSignal::new(Vec::<String>::new());
     let input_ref = NodeRef::<html::Input>::new();
 
     let perform_submit_action = move || {
@@ -89,6 +92,7 @@ pub fn GameComponent(
         input_ref,
         error_message,
         shake_trigger,
+        prompt_history,
         on_exit_game,
         on_submit: Callback::new(move |_| perform_submit_action()),
         on_skip: Callback::new(move |_| skip_turn_action()),
@@ -104,6 +108,27 @@ pub fn GameComponent(
         }
     });
 
+    // Remember each prompt of the current game, starting over when a new game begins
+    let status = Memo::new(move |_| lobby_info.get().map(|i| i.status));
+    Effect::new(move |prev: Option<Option<shared::GameStatus>>| {
+        let current = status.get();
+        if current == Some(shared::GameStatus::Playing) && prev.flatten() != current {
+            prompt_history.set(Vec::new());
+        }
+        current
+    });
+    Effect::new(move |_| {
+        let current = prompt.get();
+        if current.is_empty() || status.get_untracked() != Some(shared::GameStatus::Playing) {
+            return;
+        }
+        prompt_history.update(|history| {
+            if history.last() != Some(&current) {
+                history.push(current);
+            }
+        });
+    });
+
     // Focus input on mount
     Effect::new(move |_| {
         if let Some(input) = input_ref.get() {
@@ -7,6 +7,7 @@ pub fn PromptDisplay() -> impl IntoView {
     let in_game_context = use_context::<InGameContext>().expect("InGameContext missing");
     
     let prompt = game_context.prompt;
+    let prompt_kanji = game_context.prompt_kanji;
     let is_loading = in_game_context.is_loading;
 
     view! {
@@ -17,9 +18,22 @@ pub fn PromptDisplay() -> impl IntoView {
                 when=move || is_loading.get()
                 fallback=move || {
                     view! {
-                        <div class="text-6xl sm:text-8xl lg:text-9xl leading-tight text-gray-800 dark:text-gray-100 kanji-font select-none text-center break-words max-w-full">
+                        <div class="flex flex-col items-center gap-2 max-w-full">
+                        <div class=move || format!(
+                            "{} leading-tight text-gray-800 dark:text-gray-100 kanji-font select-none text-center break-words max-w-full",
+                            // Long prompts such as English glosses need a smaller size to fit
+                            if prompt.get().chars().count() > 8 { "text-3xl sm:text-4xl lg:text-5xl" } else { "text-6xl sm:text-8xl lg:text-9xl" }
+                        )>
                             {move || prompt.get()}
                         </div>
+                        // What each kanji means, to learn from while playing
+                        {move || prompt_kanji.get().into_iter().filter(|k| !k.meanings.is_empty()).map(|k| view! {
+                            <p class="text-sm text-gray-600 dark:text-gray-300 text-center">
+                                <span class="font-semibold">{k.meanings.join(", ")}</span>
+                                <span class="text-gray-500 dark:text-gray-400">{format!(" ({})", k.facts())}</span>
+                            </p>
+                        }).collect_view()}
+                        </div>
                     }
                 }
             >
@@ -9,7 +9,7 @@ pub fn TimerBar() -> impl IntoView {
     let progress = RwSignal::new(100.0);
     let expires_at = game_context.expires_at;
     let time_limit = Memo::new(move |_| {
-        lobby_info.get().and_then(|info| info.settings.time_limit_seconds)
+        lobby_info.get().and_then(|info| info.settings.timer_seconds())
     });
     let is_active = Memo::new(move |_| time_limit.get().unwrap_or(0) > 0);
 
@@ -0,0 +1,92 @@
+use leptos::prelude::*;
+use shared::{Bracket, BracketMatch};
+use crate::{context::GameContext, styled_view};
+
+styled_view!(round_column, "flex flex-col justify-around gap-2 min-w-[9rem]");
+styled_view!(match_card, "rounded border border-gray-200 dark:border-gray-600 bg-white dark:bg-gray-800 text-xs divide-y divide-gray-100 dark:divide-gray-700");
+styled_view!(entrant_row, is_winner: bool,
+    "px-2 py-1 truncate",
+    if is_winner { "font-bold text-green-700 dark:text-green-400" } else { "text-gray-700 dark:text-gray-300" }
+);
+
+
+fn round_name(round: usize, total: usize) -> String {
+    match total - round {
+        1 => "Final".to_string(),
+        2 => "Semifinals".to_string(),
+        _ => format!("Round {}", round + 1),
+    }
+}
+
+fn match_view(m: BracketMatch, round: usize) -> impl IntoView {
+    let winner = m.winner.clone();
+    let rows = m.players.into_iter().map(|slot| {
+        let is_winner = slot.as_ref().is_some_and(|e| Some(&e.id) == winner.as_ref());
+        let label = match slot {
+            Some(entrant) => entrant.name,
+            // Only first-round slots can be byes; later ones wait on an earlier match
+            None if round == 0 => "bye".to_string(),
+            None => "TBD".to_string(),
+        };
+        view! { <div class=entrant_row(is_winner)>{label}</div> }
+    }).collect_view();
+
+    view! { <div class=match_card()>{rows}</div> }
+}
+
+/// The tournament bracket, with a shortcut into the viewer's current match.
+#[component]
+pub fn BracketView() -> impl IntoView {
+    let game_context = use_context::<GameContext>().expect("GameContext missing");
+    let lobby_info = game_context.lobby_info;
+    let lobby_id = game_context.lobby_id;
+    let player_id = game_context.player_id;
+    let enter_lobby = game_context.enter_lobby;
+
+    let bracket = Signal::derive(move || lobby_info.get().and_then(|i| i.bracket));
+
+    // The viewer's undecided match, when they aren't already in its lobby
+    let my_match = Signal::derive(move || {
+        bracket.get()
+            .and_then(|b| b.active_match_of(&player_id.get()).and_then(|m| m.lobby_id.clone()))
+            .filter(|id| *id != lobby_id.get())
+    });
+
+    let status = move |b: &Bracket| {
+        if b.cancelled {
+            "A match was abandoned, so the tournament was cancelled.".to_string()
+        } else if let Some(champion) = b.champion() {
+            format!("🏆 {} wins the tournament!", champion.name)
+        } else {
+            "Tournament in progress".to_string()
+        }
+    };
+
+    view! {
+        {move || bracket.get().map(|b| {
+            let total = b.rounds.len();
+            view! {
+                <div class="space-y-3">
+                    <h3 class="text-xl font-semibold text-blue-600 dark:text-blue-400 border-b border-gray-200 dark:border-gray-700 pb-2">"Bracket"</h3>
+                    <p class="text-sm text-gray-600 dark:text-gray-300">{status(&b)}</p>
+                    <div class="flex gap-4 overflow-x-auto pb-2">
+                        {b.rounds.into_iter().enumerate().map(|(round, matches)| view! {
+                            <div class=round_column()>
+                                <p class="text-xs font-semibold uppercase tracking-wider text-gray-500 dark:text-gray-400">{round_name(round, total)}</p>
+                                {matches.into_iter().map(|m| match_view(m, round)).collect_view()}
+                            </div>
+                        }).collect_view()}
+                    </div>
+                    {move || my_match.get().map(|target| view! {
+                        <button
+                            on:click=move |_| enter_lobby.run(target.clone())
+                            class="w-full bg-green-500 hover:bg-green-600 text-white font-semibold py-2 px-4 rounded transition-colors"
+                        >
+                            "Go to your match"
+                        </button>
+                    })}
+                </div>
+            }
+        })}
+    }
+}
@@ -1,13 +1,13 @@
 // Component for managing lobby state
 use crate::{
-    components::lobby::{settings::{LobbySettingsPanel, use_lobby_settings}, ChatComponent},
+    components::lobby::{settings::{LobbySettingsPanel, use_lobby_settings}, BracketView, ChatComponent, ModeToggle, ReviewQueuePanel, SeriesScoreboard},
     styled_view,
     context::GameContext,
     components::toast::{use_toast, ToastType},
 };
 use leptos::ev;
 use leptos::prelude::*;
-use shared::{StartGameRequest, GameMode, start_game};
+use shared::{BotProfile, StartGameRequest, GameMode, start_game};
 use wasm_bindgen_futures::spawn_local;
 
 styled_view!(lobby_container, "max-w-2xl mx-auto my-8 p-8 bg-white dark:bg-gray-800 rounded-lg shadow-lg transition-colors");
@@ -155,6 +155,9 @@ fn LobbyDetails(
         >
             <div class="space-y-6">
                 <PlayersList set_is_loading=set_is_loading set_status=set_status />
+                <BracketView />
+                <SeriesScoreboard />
+                <ReviewQueuePanel />
                 <ChatComponent />
                 <LobbySettingsPanel settings=settings on_update=on_update />
                 <LobbyActions on_start_game=on_start_game on_leave_lobby=on_leave_lobby />
@@ -190,6 +193,7 @@ fn PlayersList(set_is_loading: WriteSignal<bool>, set_status: WriteSignal<String
                         let id_for_leader = player.id.clone();
                         let id_for_you = player.id.clone();
                         let id_for_actions = player.id.clone();
+                        let is_bot = player.is_bot;
 
                         let on_kick = {
                             let r_id = current_player_id.get();
@@ -223,7 +227,12 @@ fn PlayersList(set_is_loading: WriteSignal<bool>, set_status: WriteSignal<String
 
                         view! {
                             <li class=move || player_item(id_for_item == current_player_id.get())>
-                                <span class="font-medium">{player.name}</span>
+                                <span class="font-medium">
+                                    {player.name}
+                                    <Show when=move || is_bot>
+                                        <span class="ml-1" title="Bot">"🤖"</span>
+                                    </Show>
+                                </span>
                                 <div class="flex items-center gap-2">
                                     <Show when=move || id_for_leader == leader_id.get()>
                                         <span class="text-lg" title="Lobby Leader">
@@ -245,6 +254,7 @@ fn PlayersList(set_is_loading: WriteSignal<bool>, set_status: WriteSignal<String
                                         </button>
                                         <button
                                             on:click=on_promote.clone()
+                                            class:hidden=is_bot
                                             class="px-2 py-1 text-xs font-semibold rounded bg-blue-100 dark:bg-blue-900/30 text-blue-700 dark:text-blue-400 hover:bg-blue-200 dark:hover:bg-blue-800 transition-colors"
                                             title="Make Leader"
                                         >
@@ -257,6 +267,74 @@ fn PlayersList(set_is_loading: WriteSignal<bool>, set_status: WriteSignal<String
                     }
                 />
             </ul>
+            <Show when=move || is_leader.get() && !lobby_info.get().is_some_and(|i| i.settings.mode.is_solo())>
+                <AddBotPanel set_is_loading=set_is_loading set_status=set_status />
+            </Show>
+        </div>
+    }
+}
+
+/// Leader controls for filling the lobby with bot opponents.
+#[component]
+fn AddBotPanel(set_is_loading: WriteSignal<bool>, set_status: WriteSignal<String>) -> impl IntoView {
+    let game_context = use_context::<GameContext>().expect("GameContext missing");
+    let lobby_info = game_context.lobby_info;
+    let player_id = game_context.player_id;
+    let run_api_action = crate::hooks::use_api_action(set_is_loading, set_status);
+
+    let profile = RwSignal::new(BotProfile::default());
+
+    let on_add = move |_| {
+        let l_id = lobby_info.get().map(|i| i.lobby_id).unwrap_or_default();
+        let r_id = player_id.get();
+        let profile = profile.get();
+        run_api_action(Box::pin(async move {
+            shared::add_bot(l_id, r_id, profile).await?;
+            Ok(())
+        }));
+    };
+
+    view! {
+        <div class="space-y-2 p-3 rounded border border-gray-200 dark:border-gray-600">
+            <ModeToggle
+                selected=Signal::derive(move || profile.get())
+                options=vec![
+                    (BotProfile::easy(), "Easy"),
+                    (BotProfile::medium(), "Medium"),
+                    (BotProfile::hard(), "Hard"),
+                ]
+                on_change=Callback::new(move |p| profile.set(p))
+            />
+            <label class="block text-xs text-gray-600 dark:text-gray-300">
+                {move || format!("Accuracy: {}%", profile.get().accuracy)}
+                <input
+                    type="range" min="0" max="100" class="w-full"
+                    prop:value=move || profile.get().accuracy
+                    on:input=move |ev| {
+                        if let Ok(v) = event_target_value(&ev).parse::<u8>() {
+                            profile.update(|p| p.accuracy = v);
+                        }
+                    }
+                />
+            </label>
+            <label class="block text-xs text-gray-600 dark:text-gray-300">
+                {move || format!("Prefers common words: {}%", profile.get().common_bias)}
+                <input
+                    type="range" min="0" max="100" class="w-full"
+                    prop:value=move || profile.get().common_bias
+                    on:input=move |ev| {
+                        if let Ok(v) = event_target_value(&ev).parse::<u8>() {
+                            profile.update(|p| p.common_bias = v);
+                        }
+                    }
+                />
+            </label>
+            <button
+                on:click=on_add
+                class="w-full py-1 px-4 text-sm font-semibold rounded bg-gray-100 dark:bg-gray-700 hover:bg-gray-200 dark:hover:bg-gray-600 text-gray-800 dark:text-gray-100 transition-colors"
+            >
+                "Add Bot"
+            </button>
         </div>
     }
 }
@@ -275,9 +353,13 @@ fn LobbyActions(
     let game_mode = Signal::derive(move || lobby_info.get().map(|i| i.settings.mode).unwrap_or(GameMode::Zen));
 
     let not_enough_players = Signal::derive(move || match game_mode.get() {
-        GameMode::Duel | GameMode::Deathmatch => player_count.get() < 2 ,
+        GameMode::Duel | GameMode::Deathmatch | GameMode::Shiritori | GameMode::Tournament | GameMode::Bomb => player_count.get() < 2 ,
+        GameMode::TimeAttack | GameMode::Daily | GameMode::Review => player_count.get() != 1,
         _ => false,
     });
+    let tournament_running = Signal::derive(move || {
+        lobby_info.get().and_then(|i| i.bracket).is_some_and(|b| b.is_running())
+    });
 
     view! {
         <div class="flex flex-col gap-4 my-6">
@@ -293,15 +375,18 @@ fn LobbyActions(
             >
                 <button
                     on:click=move |ev| on_start_game.run(ev)
-                    disabled=move || not_enough_players.get()
+                    disabled=move || not_enough_players.get() || tournament_running.get()
                     class=btn_start_game()
                 >
-                    "Start Game"
+                    {move || if game_mode.get() == GameMode::Tournament { "Start Tournament" } else { "Start Game" }}
                 </button>
                 <Show when=move || not_enough_players.get()>
                     <p class="text-orange-600 dark:text-orange-400 text-center font-medium">
                         {move || match game_mode.get() {
-                            GameMode::Duel | GameMode::Deathmatch => "Need at least 2 players to start",
+                            GameMode::Duel | GameMode::Deathmatch | GameMode::Shiritori | GameMode::Tournament | GameMode::Bomb => "Need at least 2 players to start",
+                            GameMode::TimeAttack => "Time Attack is single-player only",
+                            GameMode::Daily => "The daily challenge is single-player only",
+                            GameMode::Review => "Review is single-player only",
                             _ => "",
                         }}
                     </p>
@@ -6,11 +6,17 @@ pub mod mode_toggle;
 pub mod settings_grid;
 pub mod public_list;
 pub mod chat;
+pub mod bracket;
+pub mod series;
+pub mod review_queue;
 
 // Re-export shared components
 pub use lobby_management::{GameInstructions, LobbyManagementComponent, StatusMessage};
 pub use lobby_join::LobbyJoinComponent;
 pub use public_list::PublicLobbiesList;
 pub use chat::ChatComponent;
+pub use bracket::BracketView;
+pub use series::SeriesScoreboard;
+pub use review_queue::ReviewQueuePanel;
 pub use mode_toggle::ModeToggle;
 pub use settings_grid::{SettingsGrid, SettingsItem};
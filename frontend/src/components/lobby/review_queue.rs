@@ -0,0 +1,79 @@
+use leptos::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use crate::{context::GameContext, persistence};
+
+/// The logged-in player's spaced-repetition queue, shown while Review mode is selected.
+#[component]
+pub fn ReviewQueuePanel() -> impl IntoView {
+    let game_context = use_context::<GameContext>().expect("GameContext missing");
+    let lobby_info = game_context.lobby_info;
+
+    let is_review = Signal::derive(move || lobby_info.get().is_some_and(|i| i.settings.mode == shared::GameMode::Review));
+    let token = StoredValue::new(persistence::load_auth().filter(|a| !a.is_guest).and_then(|a| a.token));
+
+    // Refetch after each game, since misses are saved as they happen
+    let status = Memo::new(move |_| lobby_info.get().map(|i| i.status));
+    let reload = RwSignal::new(0u32);
+    let queue = LocalResource::new(move || {
+        status.track();
+        reload.track();
+        let token = token.get_value();
+        async move {
+            match token {
+                Some(token) => shared::get_review_queue(token).await.ok(),
+                None => None,
+            }
+        }
+    });
+
+    let on_reset = move |_| {
+        let Some(token) = token.get_value() else { return };
+        spawn_local(async move {
+            if shared::reset_review_queue(token).await.is_ok() {
+                reload.update(|n| *n += 1);
+            }
+        });
+    };
+
+    view! {
+        <Show when=move || is_review.get()>
+            <div class="py-3 px-4 bg-gray-50 dark:bg-gray-700/50 rounded-lg space-y-2 text-left">
+                <p class="text-sm text-gray-500 dark:text-gray-400 uppercase tracking-wider font-semibold text-center">"Review Queue"</p>
+                <Transition fallback=move || view! { <p class="text-sm text-gray-500 italic">"Loading queue..."</p> }>
+                    {move || match queue.get().flatten() {
+                        None => view! {
+                            <p class="text-sm text-gray-500 italic text-center">"Log in to an account to keep a review queue."</p>
+                        }.into_any(),
+                        Some(entries) if entries.is_empty() => view! {
+                            <p class="text-sm text-gray-500 italic text-center">"Nothing to review yet. Prompts you miss or skip will show up here."</p>
+                        }.into_any(),
+                        Some(entries) => {
+                            let due = entries.iter().filter(|e| e.is_due).count();
+                            view! {
+                                <p class="text-sm text-gray-600 dark:text-gray-300 text-center">
+                                    {format!("{} due now, {} in total", due, entries.len())}
+                                </p>
+                                <ul class="max-h-48 overflow-y-auto space-y-1">
+                                    {entries.into_iter().map(|e| view! {
+                                        <li class="flex justify-between text-sm text-gray-700 dark:text-gray-200">
+                                            <span>{e.prompt}</span>
+                                            <span class=if e.is_due { "font-bold text-orange-600 dark:text-orange-400" } else { "text-gray-500" }>
+                                                {if e.is_due { "due".to_string() } else { format!("every {}d", e.interval_days) }}
+                                            </span>
+                                        </li>
+                                    }).collect_view()}
+                                </ul>
+                                <button
+                                    on:click=on_reset
+                                    class="w-full py-1 px-4 text-sm font-semibold rounded bg-gray-100 dark:bg-gray-700 hover:bg-gray-200 dark:hover:bg-gray-600 text-gray-800 dark:text-gray-100 transition-colors"
+                                >
+                                    "Reset Queue"
+                                </button>
+                            }.into_any()
+                        },
+                    }}
+                </Transition>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,47 @@
+use leptos::prelude::*;
+use shared::{GameStatus, SeriesStandings};
+use crate::context::GameContext;
+
+fn series_status(series: &SeriesStandings, status: GameStatus) -> String {
+    if let Some(champion) = series.champion.as_ref() {
+        let name = series.wins.iter().find(|e| &e.player_id == champion).map(|e| e.name.clone()).unwrap_or_default();
+        format!("🏆 {} wins the series!", name)
+    } else if status == GameStatus::Finished {
+        format!("Game {} starts in a few seconds...", series.games_played + 1)
+    } else {
+        format!("First to {} wins, or most wins after {} games", series.wins_needed(), series.best_of)
+    }
+}
+
+/// Game wins so far in the lobby's best-of-N series.
+#[component]
+pub fn SeriesScoreboard() -> impl IntoView {
+    let game_context = use_context::<GameContext>().expect("GameContext missing");
+    let lobby_info = game_context.lobby_info;
+
+    let series = Signal::derive(move || lobby_info.get().and_then(|i| i.series));
+    let status = Signal::derive(move || lobby_info.get().map(|i| i.status).unwrap_or_default());
+
+    view! {
+        {move || series.get().map(|s| {
+            let mut entries = s.wins.clone();
+            entries.sort_by_key(|e| std::cmp::Reverse(e.wins));
+            view! {
+                <div class="py-3 px-4 bg-gray-50 dark:bg-gray-700/50 rounded-lg space-y-1 text-left">
+                    <p class="text-sm text-gray-500 dark:text-gray-400 uppercase tracking-wider font-semibold text-center">
+                        {format!("Best of {}", s.best_of)}
+                    </p>
+                    <ul class="space-y-1">
+                        {entries.into_iter().map(|e| view! {
+                            <li class="flex justify-between text-sm text-gray-700 dark:text-gray-200">
+                                <span>{e.name}</span>
+                                <span class="font-bold">{e.wins}</span>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                    <p class="text-sm text-gray-600 dark:text-gray-300 text-center">{series_status(&s, status.get())}</p>
+                </div>
+            }
+        })}
+    }
+}
@@ -1,6 +1,6 @@
 use crate::styled_view;
 use leptos::prelude::*;
-use shared::{GameSettings, UpdateSettingsRequest, update_lobby_settings};
+use shared::{DifficultySet, GameSettings, PlayerId, Team, UpdateSettingsRequest, update_lobby_settings};
 use crate::context::GameContext;
 use super::{ModeToggle, SettingsGrid, SettingsItem};
 
@@ -21,6 +21,19 @@ styled_view!(difficulty_btn, is_active: bool,
     if is_active { "bg-blue-500 dark:bg-blue-600 text-white border-blue-600 dark:border-blue-700" } else { "bg-white dark:bg-gray-800 text-gray-600 dark:text-gray-300 border-gray-300 dark:border-gray-600 hover:bg-gray-100 dark:hover:bg-gray-700" }
 );
 
+const TEAM_NAMES: [&str; 4] = ["Red", "Blue", "Green", "Yellow"];
+
+/// Deal players round-robin into `count` freshly named teams.
+fn split_into_teams(players: &[PlayerId], count: usize) -> Vec<Team> {
+    let mut teams: Vec<Team> = TEAM_NAMES.iter().take(count)
+        .map(|name| Team { name: name.to_string(), members: Vec::new() })
+        .collect();
+    for (i, player) in players.iter().enumerate() {
+        teams[i % count].members.push(player.clone());
+    }
+    teams
+}
+
 /// Hook to handle updating lobby settings
 pub fn use_lobby_settings(
     set_is_loading: WriteSignal<bool>,
@@ -58,9 +71,54 @@ pub fn LobbySettingsPanel(
 {
     let game_context = use_context::<GameContext>().expect("GameContext missing");
     let is_leader = game_context.is_leader;
+    let lobby_info = game_context.lobby_info;
+
+    // Modes the server lacks the data for are left out; a stub lobby without the list yet shows them all
+    let content_modes = Memo::new(move |_| lobby_info.get().map(|i| i.content_modes).unwrap_or_default());
+
+    let active_players = Signal::derive(move || {
+        lobby_info.get()
+            .map(|i| i.players.into_iter().filter(|p| !p.is_spectator).collect::<Vec<_>>())
+            .unwrap_or_default()
+    });
+
+    let set_team_count = move |count: usize| {
+        if !is_leader.get() { return; }
+        let mut new_settings = settings.get();
+        new_settings.teams = if count == 0 {
+            Vec::new()
+        } else {
+            let ids: Vec<PlayerId> = active_players.get().into_iter().map(|p| p.id).collect();
+            split_into_teams(&ids, count)
+        };
+        on_update.run(new_settings);
+    };
+
+    let move_to_team = move |player: PlayerId, team_idx: usize| {
+        if !is_leader.get() { return; }
+        let mut new_settings = settings.get();
+        for team in new_settings.teams.iter_mut() {
+            team.members.retain(|m| m != &player);
+        }
+        if let Some(team) = new_settings.teams.get_mut(team_idx) {
+            team.members.push(player);
+        }
+        on_update.run(new_settings);
+    };
+
+    let rename_team = move |team_idx: usize, name: String| {
+        if !is_leader.get() { return; }
+        let name = name.trim().to_string();
+        let mut new_settings = settings.get();
+        if name.is_empty() || new_settings.teams.iter().any(|t| t.name == name) { return; }
+        if let Some(team) = new_settings.teams.get_mut(team_idx) {
+            team.name = name;
+            on_update.run(new_settings);
+        }
+    };
     // Handler for toggling difficulty
     let toggle_difficulty = {
-        move |level: String| {
+        move |level: DifficultySet| {
             if !is_leader.get() { return; }
             let mut new_settings = settings.get();
             if new_settings.difficulty_levels.contains(&level) {
@@ -97,6 +155,13 @@ pub fn LobbySettingsPanel(
                             (shared::GameMode::Deathmatch, "Deathmatch"),
                             (shared::GameMode::Duel, "Duel"),
                             (shared::GameMode::Zen, "Zen"),
+                            (shared::GameMode::Shiritori, "Shiritori"),
+                            (shared::GameMode::TimeAttack, "Time Attack"),
+                            (shared::GameMode::Survival, "Survival"),
+                            (shared::GameMode::Daily, "Daily"),
+                            (shared::GameMode::Tournament, "Tournament"),
+                            (shared::GameMode::Bomb, "Bomb"),
+                            (shared::GameMode::Review, "Review"),
                         ]
                         on_change=Callback::new(move |mode| {
                             let mut new_settings = settings.get();
@@ -106,21 +171,161 @@ pub fn LobbySettingsPanel(
                     />
                 </SettingsItem>
 
-                <SettingsItem label="Content Type">
-                    <ModeToggle 
-                        selected=Signal::derive(move || settings.get().content_mode)
+                <Show when=move || settings.get().mode == shared::GameMode::Daily>
+                    <SettingsItem label="Daily Challenge">
+                        <p class="text-xs text-gray-400">"Everyone gets the same kanji today: all JLPT levels, one 60 second run. Only your first attempt each day counts towards the leaderboard."</p>
+                    </SettingsItem>
+                </Show>
+
+                <Show when=move || settings.get().mode == shared::GameMode::Review>
+                    <SettingsItem label="Review">
+                        <p class="text-xs text-gray-400">"Prompts you missed or skipped come back when they are due, spaced further apart each time you get them right. New prompts fill in once you are caught up."</p>
+                    </SettingsItem>
+                </Show>
+
+                <Show when=move || !matches!(settings.get().mode, shared::GameMode::Shiritori | shared::GameMode::Daily)>
+                    <SettingsItem label="Content Type">
+                        {move || {
+                            let available = content_modes.get();
+                            let options = vec![
+                                (shared::ContentMode::Kanji, "Kanji"),
+                                (shared::ContentMode::Compound, "Compound"),
+                                (shared::ContentMode::KanjiReading, "Readings"),
+                                (shared::ContentMode::Vocab, "Vocab"),
+                                (shared::ContentMode::ReverseVocab, "Spelling"),
+                                (shared::ContentMode::Meaning, "Meaning"),
+                            ].into_iter().filter(|(mode, _)| available.is_empty() || available.contains(mode)).collect::<Vec<_>>();
+                            view! {
+                                <ModeToggle
+                                    selected=Signal::derive(move || settings.get().content_mode)
+                                    options=options
+                                    on_change=Callback::new(move |content| {
+                                        let mut new_settings = settings.get();
+                                        new_settings.content_mode = content;
+                                        on_update.run(new_settings);
+                                    })
+                                />
+                            }
+                        }}
+                    </SettingsItem>
+                </Show>
+
+                <Show when=move || settings.get().content_mode == shared::ContentMode::KanjiReading && settings.get().mode != shared::GameMode::Shiritori>
+                    <SettingsItem label="Accepted Readings">
+                        <ModeToggle
+                            selected=Signal::derive(move || settings.get().reading_type)
+                            options=vec![
+                                (shared::ReadingType::Any, "Either"),
+                                (shared::ReadingType::Onyomi, "On'yomi"),
+                                (shared::ReadingType::Kunyomi, "Kun'yomi"),
+                            ]
+                            on_change=Callback::new(move |reading_type| {
+                                let mut new_settings = settings.get();
+                                new_settings.reading_type = reading_type;
+                                on_update.run(new_settings);
+                            })
+                        />
+                    </SettingsItem>
+                </Show>
+
+                <Show when=move || matches!(settings.get().content_mode, shared::ContentMode::Kanji | shared::ContentMode::Compound) && settings.get().mode != shared::GameMode::Shiritori>
+                    <SettingsItem label="Inflections">
+                        <div class="flex items-center justify-between p-2 bg-white dark:bg-gray-800 rounded border border-gray-200 dark:border-gray-600">
+                            <span class="text-xs text-gray-600 dark:text-gray-300">"Accept conjugated forms (食べた, 高くない)"</span>
+                            <button
+                                on:click=move |_| {
+                                    if !is_leader.get() { return; }
+                                    let mut new_settings = settings.get();
+                                    new_settings.exact_headwords = !new_settings.exact_headwords;
+                                    on_update.run(new_settings);
+                                }
+                                disabled=move || !is_leader.get()
+                                class=move || toggle_switch(!settings.get().exact_headwords)
+                            >
+                                <span class=move || toggle_knob(!settings.get().exact_headwords) />
+                            </button>
+                        </div>
+                    </SettingsItem>
+                    <SettingsItem label="Dictionary">
+                        <div class="flex items-center justify-between p-2 bg-white dark:bg-gray-800 rounded border border-gray-200 dark:border-gray-600">
+                            <span class="text-xs text-gray-600 dark:text-gray-300">
+                                {move || if settings.get().full_dictionary { "Full dictionary (archaic and rare words count)" } else { "Common words only" }}
+                            </span>
+                            <button
+                                on:click=move |_| {
+                                    if !is_leader.get() { return; }
+                                    let mut new_settings = settings.get();
+                                    new_settings.full_dictionary = !new_settings.full_dictionary;
+                                    on_update.run(new_settings);
+                                }
+                                disabled=move || !is_leader.get()
+                                class=move || toggle_switch(settings.get().full_dictionary)
+                            >
+                                <span class=move || toggle_knob(settings.get().full_dictionary) />
+                            </button>
+                        </div>
+                    </SettingsItem>
+                </Show>
+
+                <Show when=move || settings.get().content_mode == shared::ContentMode::Vocab && settings.get().mode != shared::GameMode::Shiritori>
+                    <SettingsItem label="Romaji">
+                        <div class="flex items-center justify-between p-2 bg-white dark:bg-gray-800 rounded border border-gray-200 dark:border-gray-600">
+                            <span class="text-xs text-gray-600 dark:text-gray-300">"Accept romaji answers"</span>
+                            <button
+                                on:click=move |_| {
+                                    if !is_leader.get() { return; }
+                                    let mut new_settings = settings.get();
+                                    new_settings.allow_romaji = !new_settings.allow_romaji;
+                                    on_update.run(new_settings);
+                                }
+                                disabled=move || !is_leader.get()
+                                class=move || toggle_switch(settings.get().allow_romaji)
+                            >
+                                <span class=move || toggle_knob(settings.get().allow_romaji) />
+                            </button>
+                        </div>
+                    </SettingsItem>
+                </Show>
+
+                <SettingsItem label="Answer Checking">
+                    <ModeToggle
+                        selected=Signal::derive(move || settings.get().answer_strictness)
                         options=vec![
-                            (shared::ContentMode::Kanji, "Kanji"),
-                            (shared::ContentMode::Vocab, "Vocab"),
+                            (shared::AnswerStrictness::Strict, "Strict"),
+                            (shared::AnswerStrictness::Standard, "Standard"),
+                            (shared::AnswerStrictness::Lenient, "Lenient"),
                         ]
-                        on_change=Callback::new(move |content| {
+                        on_change=Callback::new(move |strictness| {
                             let mut new_settings = settings.get();
-                            new_settings.content_mode = content;
+                            new_settings.answer_strictness = strictness;
                             on_update.run(new_settings);
                         })
                     />
+                    <p class="text-xs text-gray-400 mt-1">"Standard accepts katakana for hiragana, full-width or half-width input and stray punctuation; Lenient also lets ー stand for its vowel."</p>
                 </SettingsItem>
 
+                <Show when=move || {
+                    let s = settings.get();
+                    (s.mode == shared::GameMode::Deathmatch || s.mode.is_turn_based()) && !s.is_team_game()
+                }>
+                    <SettingsItem label="Series">
+                        <ModeToggle
+                            selected=Signal::derive(move || settings.get().series_length.filter(|&n| n > 1).unwrap_or(1))
+                            options=vec![
+                                (1, "Single Game"),
+                                (3, "Best of 3"),
+                                (5, "Best of 5"),
+                            ]
+                            on_change=Callback::new(move |length: u32| {
+                                let mut new_settings = settings.get();
+                                new_settings.series_length = (length > 1).then_some(length);
+                                on_update.run(new_settings);
+                            })
+                        />
+                        <p class="text-xs text-gray-400 mt-1">"The next game starts automatically until someone has won the majority."</p>
+                    </SettingsItem>
+                </Show>
+
                 // --- Mode Specific Settings ---
                 <Show when=move || settings.get().mode == shared::GameMode::Deathmatch>
                     <SettingsItem label="Target Score">
@@ -141,11 +346,75 @@ pub fn LobbySettingsPanel(
                             disabled=move || !is_leader.get()
                             class=input_field()
                         />
-                         <p class="text-xs text-gray-400 mt-1">"First player to reach this score wins."</p>
+                         <p class="text-xs text-gray-400 mt-1">
+                            {move || if settings.get().teams.is_empty() { "First player to reach this score wins." } else { "First team to reach this combined score wins." }}
+                         </p>
+                    </SettingsItem>
+
+                    <SettingsItem label="Teams">
+                        <div class="space-y-3">
+                            <div class="flex gap-2 flex-wrap">
+                                {[(0, "Solo"), (2, "2 Teams"), (3, "3 Teams"), (4, "4 Teams")].into_iter().map(|(count, label)| {
+                                    view! {
+                                        <button
+                                            on:click=move |_| set_team_count(count)
+                                            disabled=move || !is_leader.get()
+                                            class=move || difficulty_btn(settings.get().teams.len() == count)
+                                        >
+                                            {label}
+                                        </button>
+                                    }
+                                }).collect_view()}
+                            </div>
+
+                            <Show when=move || !settings.get().teams.is_empty()>
+                                <div class="space-y-2">
+                                    {move || settings.get().teams.into_iter().enumerate().map(|(idx, team)| {
+                                        view! {
+                                            <input
+                                                type="text"
+                                                value=team.name.clone()
+                                                on:change=move |ev| rename_team(idx, event_target_value(&ev))
+                                                disabled=move || !is_leader.get()
+                                                class=input_field()
+                                            />
+                                        }
+                                    }).collect_view()}
+                                </div>
+                                <div class="space-y-1">
+                                    {move || {
+                                        let teams = settings.get().teams;
+                                        active_players.get().into_iter().map(|p| {
+                                            let current = teams.iter().position(|t| t.members.contains(&p.id));
+                                            let pid = p.id.clone();
+                                            view! {
+                                                <div class="flex items-center justify-between gap-2 p-2 bg-white dark:bg-gray-800 rounded border border-gray-200 dark:border-gray-600">
+                                                    <span class="text-xs text-gray-600 dark:text-gray-300 truncate">{p.name}</span>
+                                                    <select
+                                                        on:change=move |ev| {
+                                                            if let Ok(idx) = event_target_value(&ev).parse::<usize>() {
+                                                                move_to_team(pid.clone(), idx);
+                                                            }
+                                                        }
+                                                        disabled=move || !is_leader.get()
+                                                        class="text-xs p-1 border rounded dark:bg-gray-700 dark:border-gray-600 dark:text-white"
+                                                    >
+                                                        <option value="" selected=current.is_none() disabled=true>"Unassigned"</option>
+                                                        {teams.iter().enumerate().map(|(idx, t)| view! {
+                                                            <option value=idx.to_string() selected=current == Some(idx)>{t.name.clone()}</option>
+                                                        }).collect_view()}
+                                                    </select>
+                                                </div>
+                                            }
+                                        }).collect_view()
+                                    }}
+                                </div>
+                            </Show>
+                        </div>
                     </SettingsItem>
                 </Show>
 
-                <Show when=move || settings.get().mode == shared::GameMode::Duel>
+                <Show when=move || settings.get().mode.is_turn_based() || settings.get().mode == shared::GameMode::Tournament>
                     <SettingsGrid>
                         <SettingsItem label="Initial Lives">
                             <input
@@ -167,10 +436,82 @@ pub fn LobbySettingsPanel(
                             />
                         </SettingsItem>
 
+                        <Show when=move || settings.get().mode == shared::GameMode::Survival>
+                            <SettingsItem label="Streak to Level Up">
+                                <input
+                                    type="number"
+                                    min="1"
+                                    max="99"
+                                    value=move || settings.get().survival_streak.unwrap_or(5)
+                                    on:input={
+                                        move |ev| {
+                                             if !is_leader.get() { return; }
+                                             let val = event_target_value(&ev).parse::<u32>().ok();
+                                             let mut new_settings = settings.get();
+                                             new_settings.survival_streak = val;
+                                             on_update.run(new_settings);
+                                        }
+                                    }
+                                    disabled=move || !is_leader.get()
+                                    class=input_field()
+                                />
+                                <p class="text-xs text-gray-400 mt-1">"Starts at N5 and moves up a level after this many correct answers in a row."</p>
+                            </SettingsItem>
+                        </Show>
+
+                        <Show when=move || settings.get().mode == shared::GameMode::Bomb>
+                            <SettingsItem label="Fuse Length">
+                                <div class="flex items-center gap-2">
+                                    <input
+                                        type="number"
+                                        min="1"
+                                        max="300"
+                                        value=move || settings.get().fuse_range().0
+                                        on:input={
+                                            move |ev| {
+                                                 if !is_leader.get() { return; }
+                                                 let val = event_target_value(&ev).parse::<u32>().ok();
+                                                 let mut new_settings = settings.get();
+                                                 new_settings.fuse_min_seconds = val;
+                                                 on_update.run(new_settings);
+                                            }
+                                        }
+                                        disabled=move || !is_leader.get()
+                                        class=input_field()
+                                    />
+                                    <span class="text-sm text-gray-500">"to"</span>
+                                    <input
+                                        type="number"
+                                        min="1"
+                                        max="300"
+                                        value=move || settings.get().fuse_range().1
+                                        on:input={
+                                            move |ev| {
+                                                 if !is_leader.get() { return; }
+                                                 let val = event_target_value(&ev).parse::<u32>().ok();
+                                                 let mut new_settings = settings.get();
+                                                 new_settings.fuse_max_seconds = val;
+                                                 on_update.run(new_settings);
+                                            }
+                                        }
+                                        disabled=move || !is_leader.get()
+                                        class=input_field()
+                                    />
+                                    <span class="text-sm text-gray-500">"sec"</span>
+                                </div>
+                                <p class="text-xs text-gray-400 mt-1">"The bomb goes off at a random moment in this range. Whoever holds the prompt loses a life."</p>
+                            </SettingsItem>
+                        </Show>
+
+                         <Show when=move || matches!(settings.get().mode, shared::GameMode::Duel | shared::GameMode::Tournament)>
                          <SettingsItem label="Rules">
                              <div class="flex items-center justify-between p-2 bg-white dark:bg-gray-800 rounded border border-gray-200 dark:border-gray-600">
                                 <span class="text-xs text-gray-600 dark:text-gray-300">
-                                    {move || if settings.get().content_mode == shared::ContentMode::Vocab { "Reuse Word on Miss" } else { "Reuse Kanji on Miss" }}
+                                    {move || match settings.get().content_mode {
+                                        shared::ContentMode::Kanji | shared::ContentMode::Compound | shared::ContentMode::KanjiReading => "Reuse Kanji on Miss",
+                                        shared::ContentMode::Vocab | shared::ContentMode::Meaning => "Reuse Word on Miss",
+                                        shared::ContentMode::ReverseVocab => "Reuse Reading on Miss",
+                                    }}
                                 </span>
                                 <button
                                     on:click={
@@ -188,29 +529,42 @@ pub fn LobbySettingsPanel(
                                 </button>
                             </div>
                         </SettingsItem>
+                        </Show>
                     </SettingsGrid>
                 </Show>
 
                 // --- General Settings ---
-                <SettingsItem label="JLPT Levels">
-                    <div class="flex gap-2 flex-wrap">
+                <Show when=move || !matches!(settings.get().mode, shared::GameMode::Survival | shared::GameMode::Daily)>
+                <SettingsItem label="Kanji Sets">
+                    <div class="space-y-2">
                         {let diff_levels = move || settings.get().difficulty_levels;
-                         ["N1", "N2", "N3", "N4", "N5"].into_iter().map(move |level| {
-                            let level_str = level.to_string();
-                            let toggle_difficulty = toggle_difficulty;
+                         [("JLPT", true), ("School", false)].into_iter().map(move |(group, jlpt)| {
                             view! {
-                               <button
-                                   on:click=move |_| toggle_difficulty(level_str.clone())
-                                   disabled=move || !is_leader.get()
-                                   class=move || difficulty_btn(diff_levels().contains(&level.to_string()))
-                                >
-                                    {level}
-                               </button>
+                                <div class="flex gap-2 flex-wrap items-center">
+                                    <span class="text-xs text-gray-500 dark:text-gray-400 w-12">{group}</span>
+                                    {DifficultySet::ALL.into_iter().filter(move |l| l.has_words() == jlpt).map(move |level| {
+                                        let toggle_difficulty = toggle_difficulty;
+                                        view! {
+                                           <button
+                                               on:click=move |_| toggle_difficulty(level)
+                                               disabled=move || !is_leader.get()
+                                               class=move || difficulty_btn(diff_levels().contains(&level))
+                                            >
+                                                {level.label()}
+                                           </button>
+                                        }
+                                    }).collect_view()}
+                                </div>
                             }
                         }).collect_view()}
+                        <Show when=move || settings.get().content_mode.uses_words()>
+                            <p class="text-[10px] text-gray-400">"Word modes only draw from JLPT levels"</p>
+                        </Show>
                     </div>
                 </SettingsItem>
+                </Show>
 
+                <Show when=move || settings.get().mode != shared::GameMode::Daily>
                 <SettingsItem label="Randomization">
                     <div class="flex items-center justify-between p-2 bg-white dark:bg-gray-800 rounded border border-gray-200 dark:border-gray-600">
                         <span class="text-xs text-gray-600 dark:text-gray-300">"Weighted (Common first)"</span>
@@ -223,6 +577,7 @@ pub fn LobbySettingsPanel(
                         </button>
                     </div>
                 </SettingsItem>
+                </Show>
 
                 <SettingsItem label="Visibility">
                     <div class="flex items-center justify-between p-2 bg-white dark:bg-gray-800 rounded border border-gray-200 dark:border-gray-600">
@@ -245,6 +600,7 @@ pub fn LobbySettingsPanel(
                     </div>
                 </SettingsItem>
 
+                <Show when=move || !matches!(settings.get().mode, shared::GameMode::Daily | shared::GameMode::Bomb)>
                 <SettingsItem label="Timing">
                     <div class="space-y-2">
                         <div class="flex items-center gap-2">
@@ -267,9 +623,16 @@ pub fn LobbySettingsPanel(
                             />
                             <span class="text-sm text-gray-500">"sec"</span>
                         </div>
-                        <p class="text-xs text-gray-400">"Set to 0 for no limit."</p>
+                        <p class="text-xs text-gray-400">
+                            {move || if settings.get().mode == shared::GameMode::TimeAttack {
+                                "Length of the whole run. Set to 0 for the default 60 seconds."
+                            } else {
+                                "Set to 0 for no limit."
+                            }}
+                        </p>
                     </div>
                 </SettingsItem>
+                </Show>
             </SettingsGrid>
         </div>
     }
@@ -38,9 +38,25 @@ pub fn LobbyPage() -> impl IntoView {
 
     let prompt = RwSignal::new(String::new());
     let result = RwSignal::new(String::new());
+    let last_check = RwSignal::new(None::<shared::CheckWordResponse>);
+    let prompt_kanji = RwSignal::new(Vec::<shared::KanjiInfo>::new());
     let typing_status = RwSignal::new(std::collections::HashMap::<shared::PlayerId, String>::new());
     let chat_messages = RwSignal::new(Vec::<shared::ChatMessage>::new());
     let expires_at = RwSignal::new(None::<u64>);
+    let time_attack_result = RwSignal::new(None::<shared::TimeAttackResult>);
+    let daily_result = RwSignal::new(None::<shared::DailyChallengeResult>);
+
+    let navigate_match = navigate.clone();
+    let enter_lobby = Callback::new(move |target: LobbyId| {
+        save_session(&SessionData {
+            lobby_id: target.clone(),
+            player_id: player_id.get_untracked(),
+            player_name: player_name.get_untracked(),
+            is_in_game: false,
+        });
+        lobby_id.set(target.clone());
+        navigate_match(&format!("/lobby/{}", target), Default::default());
+    });
 
     let navigate_kick = navigate.clone();
     let send_message = use_shared_socket(UseSharedSocketConfig {
@@ -49,12 +65,17 @@ pub fn LobbyPage() -> impl IntoView {
         lobby_info,
         set_prompt: prompt.write_only(),
         set_result: result.write_only(),
+        set_last_check: last_check.write_only(),
+        set_prompt_kanji: prompt_kanji.write_only(),
         set_typing_status: typing_status.write_only(),
         chat_messages,
         set_expires_at: expires_at.write_only(),
+        set_time_attack_result: time_attack_result.write_only(),
+        set_daily_result: daily_result.write_only(),
         on_kicked: Some(Callback::new(move |_| {
             navigate_kick("/", Default::default());
         })),
+        on_match_ready: Some(enter_lobby),
     });
 
     provide_context(GameContext {
@@ -69,14 +90,19 @@ pub fn LobbyPage() -> impl IntoView {
         is_leader,
         prompt: prompt.read_only(),
         set_prompt: prompt.write_only(),
+        prompt_kanji: prompt_kanji.read_only(),
         result: result.read_only(),
         set_result: result.write_only(),
+        last_check: last_check.read_only(),
         typing_status: typing_status.read_only(),
         set_typing_status: typing_status.write_only(),
         chat_messages,
         expires_at: expires_at.read_only(),
         set_expires_at: expires_at.write_only(),
+        time_attack_result: time_attack_result.read_only(),
+        daily_result: daily_result.read_only(),
         send_message: Callback::new(send_message),
+        enter_lobby,
     });
 
     // UI State
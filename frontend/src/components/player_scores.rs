@@ -14,6 +14,7 @@ pub fn PlayerScoresComponent(
     let mut sorted_players: Vec<PlayerData> = players.iter().filter(|p| !p.is_spectator).cloned().collect();
     let spectators: Vec<PlayerData> = players.into_iter().filter(|p| p.is_spectator).collect();
     sorted_players.sort_by(|a, b| b.score.cmp(&a.score).then(a.name.cmp(&b.name)));
+    group_by_team(&mut sorted_players);
 
     let ranked_players: Vec<(usize, PlayerData)> = sorted_players.into_iter().enumerate().map(|(i, p)| (i + 1, p)).collect();
     let spectators_val = StoredValue::new(spectators);
@@ -43,6 +44,9 @@ pub fn PlayerScoresComponent(
                                         <span class="font-medium text-gray-900 truncate">
                                             {player.name.clone()}
                                         </span>
+                                        {player.team.clone().map(|team| view! {
+                                            <span class="text-xs bg-gray-200 text-gray-700 px-2 py-0.5 rounded-full">{team}</span>
+                                        })}
                                         <div class="flex items-center gap-1">
                                             <Show when=move || is_leader>
                                                 <span class="text-lg" title="Lobby Leader">
@@ -100,10 +104,15 @@ pub fn CompactPlayerScoresComponent() -> impl IntoView {
 
     let sorted_players = Signal::derive(move || {
         let mut p: Vec<PlayerData> = lobby_info.get().map(|i| i.players.into_iter().filter(|p| !p.is_spectator).collect()).unwrap_or_default();
-        p.sort_by(|a, b| b.score.cmp(&a.score));
+        p.sort_by_key(|p| std::cmp::Reverse(p.score));
+        group_by_team(&mut p);
         p
     });
 
+    let team_totals = Signal::derive(move || {
+        lobby_info.get().map(|i| shared::team_standings(&i.players)).unwrap_or_default()
+    });
+
     let spectators = Signal::derive(move || {
         lobby_info.get().map(|i| i.players.into_iter().filter(|p| p.is_spectator).collect::<Vec<PlayerData>>()).unwrap_or_default()
     });
@@ -120,10 +129,28 @@ pub fn CompactPlayerScoresComponent() -> impl IntoView {
         <div class="bg-gray-50 dark:bg-gray-700/50 rounded-lg p-4 mb-4 transition-colors">
             <h4 class="text-lg font-semibold text-blue-600 dark:text-blue-400 mb-3 pb-2 border-b border-gray-200 dark:border-gray-600 flex justify-between items-center">
                 <span>"Scores"</span>
-                <Show when=move || game_mode.get() == GameMode::Duel>
-                    <span class="text-xs font-normal text-gray-500 dark:text-gray-400 bg-gray-200 dark:bg-gray-600 px-2 py-1 rounded">"Duel Mode"</span>
+                <Show when=move || game_mode.get().is_turn_based()>
+                    <span class="text-xs font-normal text-gray-500 dark:text-gray-400 bg-gray-200 dark:bg-gray-600 px-2 py-1 rounded">
+                        {move || if game_mode.get() == GameMode::Shiritori { "Shiritori" } else { "Duel Mode" }}
+                    </span>
                 </Show>
             </h4>
+            <Show when=move || !team_totals.get().is_empty()>
+                <div class="flex flex-wrap gap-2 mb-3">
+                    <For
+                        each=move || team_totals.get()
+                        key=|team| team.clone()
+                        children=move |(team, total)| {
+                            view! {
+                                <div class="flex items-center gap-2 px-3 py-1 rounded-md bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 shadow-sm">
+                                    <span class="text-sm font-semibold text-gray-700 dark:text-gray-200">{team}</span>
+                                    <span class="font-bold text-blue-600 dark:text-blue-400">{total}</span>
+                                </div>
+                            }
+                        }
+                    />
+                </div>
+            </Show>
             <div class="space-y-1">
                 <For
                     each=move || sorted_players.get()
@@ -159,7 +186,7 @@ pub fn CompactPlayerScoresComponent() -> impl IntoView {
                                             let mode = game_mode.get();
                                             format!(
                                                 "w-2 h-2 rounded-full {}",
-                                                if p.is_turn && mode == GameMode::Duel { "bg-green-500 animate-pulse shadow-[0_0_8px_rgba(34,197,94,0.6)]" }
+                                                if p.is_turn && mode.is_turn_based() { "bg-green-500 animate-pulse shadow-[0_0_8px_rgba(34,197,94,0.6)]" }
                                                 else if p.is_eliminated { "bg-red-500" }
                                                 else { "bg-gray-300 dark:bg-gray-600" }
                                             )
@@ -175,6 +202,10 @@ pub fn CompactPlayerScoresComponent() -> impl IntoView {
                                                 )
                                             }>{move || player_sig.get().name.clone()}</span>
 
+                                            {move || player_sig.get().team.map(|team| view! {
+                                                <span class="text-[10px] uppercase tracking-wider font-semibold text-gray-500 dark:text-gray-400">{team}</span>
+                                            })}
+
                                             <Show when=move || player_sig.get().is_eliminated>
                                                 <span class="text-[10px] uppercase tracking-wider font-bold text-red-500">"Eliminated"</span>
                                             </Show>
@@ -182,7 +213,7 @@ pub fn CompactPlayerScoresComponent() -> impl IntoView {
                                     </div>
 
                                     <div class="flex items-center gap-6">
-                                         <Show when=move || game_mode.get() == GameMode::Duel && !player_sig.get().is_eliminated>
+                                         <Show when=move || game_mode.get().is_turn_based() && !player_sig.get().is_eliminated>
                                             <div class="flex items-center gap-1">
                                                 {move || {
                                                     let p = player_sig.get();
@@ -211,7 +242,7 @@ pub fn CompactPlayerScoresComponent() -> impl IntoView {
                                             </div>
                                          </Show>
 
-                                        <Show when=move || game_mode.get() != GameMode::Duel>
+                                        <Show when=move || !game_mode.get().is_turn_based()>
                                             <div class="flex flex-col items-end min-w-[3rem]">
                                                  <span class="text-[10px] text-gray-400 uppercase tracking-wider font-semibold">"Pts"</span>
                                                  <span class="font-bold text-2xl text-blue-600 dark:text-blue-400 leading-none">{move || player_sig.get().score}</span>
@@ -263,3 +294,13 @@ pub fn CompactPlayerScoresComponent() -> impl IntoView {
         </div>
     }
 }
+
+/// Stable-sorts players so teammates sit together, best team first.
+fn group_by_team(players: &mut [PlayerData]) {
+    let standings = shared::team_standings(players);
+    players.sort_by_key(|p| {
+        p.team.as_ref()
+            .and_then(|team| standings.iter().position(|(name, _)| name == team))
+            .unwrap_or(standings.len())
+    });
+}
@@ -20,10 +20,15 @@ pub struct UseSharedSocketConfig {
     // For now we will update global signals that Home passes to Game.
     pub set_prompt: WriteSignal<String>,
     pub set_result: WriteSignal<String>,
+    pub set_last_check: WriteSignal<Option<shared::CheckWordResponse>>,
+    pub set_prompt_kanji: WriteSignal<Vec<shared::KanjiInfo>>,
     pub set_typing_status: WriteSignal<HashMap<PlayerId, String>>,
     pub chat_messages: RwSignal<Vec<shared::ChatMessage>>,
     pub set_expires_at: WriteSignal<Option<u64>>,
+    pub set_time_attack_result: WriteSignal<Option<shared::TimeAttackResult>>,
+    pub set_daily_result: WriteSignal<Option<shared::DailyChallengeResult>>,
     pub on_kicked: Option<Callback<()>>,
+    pub on_match_ready: Option<Callback<LobbyId>>,
 }
 
 pub fn use_shared_socket(config: UseSharedSocketConfig) -> impl Fn(ClientMessage) + Copy + 'static {
@@ -34,10 +39,15 @@ pub fn use_shared_socket(config: UseSharedSocketConfig) -> impl Fn(ClientMessage
     let lobby_info_signal = config.lobby_info;
     let set_prompt = config.set_prompt;
     let set_result = config.set_result;
+    let set_last_check = config.set_last_check;
+    let set_prompt_kanji = config.set_prompt_kanji;
     let set_typing_status = config.set_typing_status;
     let chat_messages = config.chat_messages;
     let set_expires_at = config.set_expires_at;
+    let set_time_attack_result = config.set_time_attack_result;
+    let set_daily_result = config.set_daily_result;
     let on_kicked = config.on_kicked;
+    let on_match_ready = config.on_match_ready;
 
     Effect::new(move |_| {
         let lobby_id = lobby_id.get();
@@ -99,9 +109,10 @@ pub fn use_shared_socket(config: UseSharedSocketConfig) -> impl Fn(ClientMessage
                                 match serde_json::from_str::<ServerMessage>(&text) {
                                     Ok(server_msg) => {
                                     match server_msg {
-                                        ServerMessage::GameState { prompt: new_prompt, status, scores, timer_expires_at } => {
+                                        ServerMessage::GameState { prompt: new_prompt, status, scores, timer_expires_at, tier, kanji } => {
                                             leptos::logging::debug_warn!("[WS] GameState received: status={:?}, players={}", status, scores.len());
                                             set_prompt.set(new_prompt);
+                                            set_prompt_kanji.set(kanji);
                                             set_expires_at.set(timer_expires_at);
 
                                             lobby_info_signal.update(|info_opt| {
@@ -111,20 +122,25 @@ pub fn use_shared_socket(config: UseSharedSocketConfig) -> impl Fn(ClientMessage
                                                 });
                                                 info.status = status;
                                                 info.players = scores;
+                                                info.tier = tier;
                                                 *info_opt = Some(info);
                                             });
                                             set_typing_status.update(|m| m.clear());
                                             if status == GameStatus::Lobby || status == GameStatus::Playing {
                                                 set_result.set(String::new());
+                                                set_last_check.set(None);
+                                                set_time_attack_result.set(None);
+                                                set_daily_result.set(None);
                                             }
                                         },
                                         ServerMessage::WordChecked { player_id: pid, result: res } => {
                                             if pid == player_id || pid.to_string().is_empty() || pid.to_string() == "null" || pid.to_string() == "" {
-                                                let msg = res.message;
+                                                let msg = res.message.clone();
                                                 // if let Some(details) = res.error_details {
                                                 //     msg = format!("{}\nTry: {}", msg, details.join(", "));
                                                 // }
                                                 set_result.set(msg);
+                                                set_last_check.set(Some(res.clone()));
                                             }
 
                                             lobby_info_signal.update(|info_opt| {
@@ -137,12 +153,15 @@ pub fn use_shared_socket(config: UseSharedSocketConfig) -> impl Fn(ClientMessage
 
                                             if let Some(k) = res.prompt {
                                                 set_prompt.set(k);
+                                                set_prompt_kanji.set(res.prompt_kanji);
                                             }
                                             set_expires_at.set(res.timer_expires_at);
                                         },
-                                        ServerMessage::PromptUpdate { new_prompt, timer_expires_at } => {
+                                        ServerMessage::PromptUpdate { new_prompt, timer_expires_at, kanji } => {
                                             set_result.set(String::new());
+                                            set_last_check.set(None);
                                             set_prompt.set(new_prompt);
+                                            set_prompt_kanji.set(kanji);
                                             set_expires_at.set(timer_expires_at);
                                             set_typing_status.update(|m| m.clear());
                                         },
@@ -220,6 +239,51 @@ pub fn use_shared_socket(config: UseSharedSocketConfig) -> impl Fn(ClientMessage
                                                 }
                                             }
                                         },
+                                        ServerMessage::TimeAttackResult { player_id: pid, result } => {
+                                            if pid == player_id {
+                                                set_time_attack_result.set(Some(result));
+                                            }
+                                        },
+                                        ServerMessage::DailyChallengeResult { player_id: pid, result } => {
+                                            if pid == player_id {
+                                                set_daily_result.set(Some(result));
+                                            }
+                                        },
+                                        ServerMessage::BracketUpdate { bracket } => {
+                                            lobby_info_signal.update(|info_opt| {
+                                                if let Some(info) = info_opt {
+                                                    info.bracket = Some(bracket);
+                                                }
+                                            });
+                                        },
+                                        ServerMessage::TournamentMatchReady { lobby_id: match_id, player_ids } => {
+                                            if player_ids.contains(&player_id) {
+                                                toast.push.run(("Your tournament match is ready!".to_string(), ToastType::Info));
+                                                if let Some(cb) = on_match_ready {
+                                                    cb.run(match_id);
+                                                }
+                                            }
+                                        },
+                                        ServerMessage::BombExploded { player_id: pid, eliminated } => {
+                                            let name = lobby_info_signal.with_untracked(|info| {
+                                                info.as_ref().and_then(|i| i.players.iter().find(|p| p.id == pid).map(|p| p.name.clone()))
+                                            }).unwrap_or_else(|| "Someone".to_string());
+                                            let msg = match (pid == player_id, eliminated) {
+                                                (true, true) => "💥 The bomb went off in your hands! Eliminated!".to_string(),
+                                                (true, false) => "💥 The bomb went off in your hands!".to_string(),
+                                                (false, true) => format!("💥 The bomb went off on {}! Eliminated!", name),
+                                                (false, false) => format!("💥 The bomb went off on {}!", name),
+                                            };
+                                            set_result.set(msg.clone());
+                                            toast.push.run((msg, ToastType::Info));
+                                        },
+                                        ServerMessage::SeriesUpdate { series } => {
+                                            lobby_info_signal.update(|info_opt| {
+                                                if let Some(info) = info_opt {
+                                                    info.series = series;
+                                                }
+                                            });
+                                        },
                                         ServerMessage::ChatMessage(msg) => {
                                             chat_messages.update(|msgs| {
                                                 msgs.push(msg);
@@ -31,14 +31,22 @@ pub struct GameContext {
     // Game Specific (Shared via WS)
     pub prompt: ReadSignal<String>,
     pub set_prompt: WriteSignal<String>,
+    /// KANJIDIC2 details for the kanji in `prompt`
+    pub prompt_kanji: ReadSignal<Vec<shared::KanjiInfo>>,
     pub result: ReadSignal<String>,
     pub set_result: WriteSignal<String>,
+    /// Our latest checked answer, for showing the accepted word or suggestions with their readings
+    pub last_check: ReadSignal<Option<shared::CheckWordResponse>>,
     pub typing_status: ReadSignal<std::collections::HashMap<shared::PlayerId, String>>,
     pub set_typing_status: WriteSignal<std::collections::HashMap<shared::PlayerId, String>>,
     pub chat_messages: RwSignal<Vec<shared::ChatMessage>>,
     pub expires_at: ReadSignal<Option<u64>>,
     pub set_expires_at: WriteSignal<Option<u64>>,
+    pub time_attack_result: ReadSignal<Option<shared::TimeAttackResult>>,
+    pub daily_result: ReadSignal<Option<shared::DailyChallengeResult>>,
     pub send_message: Callback<shared::ClientMessage>,
+    /// Move to another lobby of the same tournament, keeping this player's id
+    pub enter_lobby: Callback<shared::LobbyId>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -48,6 +56,8 @@ pub struct InGameContext {
     pub input_ref: NodeRef<leptos::html::Input>,
     pub error_message: RwSignal<String>,
     pub shake_trigger: RwSignal<bool>,
+    /// Prompts shown so far this game, oldest first
+    pub prompt_history: RwSignal<Vec<String>>,
     pub on_exit_game: Callback<()>,
 
     // Actions
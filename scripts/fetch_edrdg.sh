@@ -0,0 +1,22 @@
+#!/bin/sh
+# Downloads the EDRDG dictionary files the server reads from data/ when present:
+#   JMdict_e.xml - words, readings, parts of speech, priority tags and English glosses
+#                  (Meaning mode, and tag-based word filtering)
+#   kanjidic2.xml - kanji readings, meanings, stroke counts, grades and radicals
+#                   (KanjiReading mode)
+# The files are published by the Electronic Dictionary Research and Development Group
+# under CC BY-SA 4.0: https://www.edrdg.org/edrdg/licence.html
+# Run from the repository root: sh scripts/fetch_edrdg.sh
+set -eu
+
+DATA_DIR="$(dirname "$0")/../data"
+BASE_URL="http://ftp.edrdg.org/pub/Nihongo"
+
+fetch() {
+    echo "Fetching $1 into $DATA_DIR/$2"
+    curl -fsSL "$BASE_URL/$1" | gunzip > "$DATA_DIR/$2.tmp"
+    mv "$DATA_DIR/$2.tmp" "$DATA_DIR/$2"
+}
+
+fetch JMdict_e.gz JMdict_e.xml
+fetch kanjidic2.xml.gz kanjidic2.xml
@@ -0,0 +1,49 @@
+#!/usr/bin/env python3
+"""Writes data/jlpt_word_order.csv, the order weighted Vocab games fall back to without word frequencies.
+
+This is not frequency data. It is derived only from files already in data/:
+JLPT words are ordered by level (N5 first), then by the frequency rank of their
+rarest kanji in the N*_kanji.csv lists (kana-only words first), then by length.
+A word on more than one list counts at its easiest level.
+
+Run from the repository root: python3 scripts/jlpt_word_order.py
+"""
+import csv
+from pathlib import Path
+
+DATA = Path(__file__).resolve().parent.parent / "data"
+
+
+def is_kanji(c):
+    return "一" <= c <= "鿿" or c == "々"
+
+
+def main():
+    kanji_rank = {}
+    for n in range(1, 6):
+        with open(DATA / f"N{n}_kanji.csv", encoding="utf-8") as f:
+            for row in csv.reader(f):
+                if len(row) >= 2 and row[1].strip().isdigit() and int(row[1]) > 0:
+                    k, r = row[0].strip(), int(row[1])
+                    kanji_rank[k] = min(kanji_rank.get(k, r), r)
+    unranked = max(kanji_rank.values()) + 1
+
+    level = {}
+    for n in range(5, 0, -1):
+        with open(DATA / f"N{n}_words.csv", encoding="utf-8") as f:
+            for row in csv.reader(f):
+                if row and row[0].strip():
+                    level.setdefault(row[0].strip(), n)
+
+    def key(word):
+        ranks = [kanji_rank.get(c, unranked) for c in word if is_kanji(c)]
+        return (-level[word], max(ranks, default=0), len(word), word)
+
+    with open(DATA / "jlpt_word_order.csv", "w", encoding="utf-8", newline="\n") as f:
+        f.write("word,order\n")
+        for i, word in enumerate(sorted(level, key=key), 1):
+            f.write(f"{word},{i}\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -1,7 +1,8 @@
 use leptos::prelude::*;
 use crate::{
     LobbyId, PlayerId, LobbyInfo, JoinLobbyRequest, StartGameRequest,
-    UpdateSettingsRequest, PlayerData, PromptResponse, LobbySummary
+    UpdateSettingsRequest, PlayerData, PromptResponse, LobbySummary, DailyLeaderboard, BotProfile,
+    ReviewQueueEntry, WordInfo
 };
 
 #[cfg(feature = "ssr")]
@@ -35,7 +36,12 @@ pub trait ApiContext: Send + Sync {
     async fn set_player_connected(&self, lobby_id: LobbyId, player_id: PlayerId, is_connected: bool) -> JsonResult;
     async fn kick_player(&self, lobby_id: LobbyId, requestor_id: PlayerId, target_player_id: PlayerId) -> JsonResult;
     async fn promote_leader(&self, lobby_id: LobbyId, requestor_id: PlayerId, target_player_id: PlayerId) -> JsonResult;
+    async fn add_bot(&self, lobby_id: LobbyId, requestor_id: PlayerId, profile: BotProfile) -> JsonResult;
     async fn get_public_lobbies(&self) -> Result<Vec<LobbySummary>, ServerFnError>;
+    async fn get_daily_leaderboard(&self) -> Result<DailyLeaderboard, ServerFnError>;
+    async fn get_review_queue(&self, token: String) -> Result<Vec<ReviewQueueEntry>, ServerFnError>;
+    async fn reset_review_queue(&self, token: String) -> JsonResult;
+    async fn lookup_kanji_words(&self, kanji: String, limit: usize) -> Result<Vec<WordInfo>, ServerFnError>;
 }
 
 #[cfg(feature = "ssr")]
@@ -124,7 +130,32 @@ pub async fn promote_leader(lobby_id: LobbyId, requestor_id: PlayerId, target_pl
     get_api_context()?.promote_leader(lobby_id, requestor_id, target_player_id).await
 }
 
+#[server(endpoint = "/api/add_bot")]
+pub async fn add_bot(lobby_id: LobbyId, requestor_id: PlayerId, profile: BotProfile) -> JsonResult {
+    get_api_context()?.add_bot(lobby_id, requestor_id, profile).await
+}
+
 #[server(endpoint = "/api/get_public_lobbies")]
 pub async fn get_public_lobbies() -> Result<Vec<LobbySummary>, ServerFnError> {
     get_api_context()?.get_public_lobbies().await
 }
+
+#[server(endpoint = "/api/get_daily_leaderboard")]
+pub async fn get_daily_leaderboard() -> Result<DailyLeaderboard, ServerFnError> {
+    get_api_context()?.get_daily_leaderboard().await
+}
+
+#[server(endpoint = "/api/get_review_queue")]
+pub async fn get_review_queue(token: String) -> Result<Vec<ReviewQueueEntry>, ServerFnError> {
+    get_api_context()?.get_review_queue(token).await
+}
+
+#[server(endpoint = "/api/reset_review_queue")]
+pub async fn reset_review_queue(token: String) -> JsonResult {
+    get_api_context()?.reset_review_queue(token).await
+}
+
+#[server(endpoint = "/api/lookup_kanji_words")]
+pub async fn lookup_kanji_words(kanji: String, limit: usize) -> Result<Vec<WordInfo>, ServerFnError> {
+    get_api_context()?.lookup_kanji_words(kanji, limit).await
+}
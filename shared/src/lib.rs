@@ -83,6 +83,12 @@ pub enum ServerMessage {
         status: GameStatus,
         scores: Vec<PlayerData>,
         timer_expires_at: Option<u64>,
+        /// JLPT level currently being played in Survival, e.g. "N4"
+        #[serde(default)]
+        tier: Option<String>,
+        /// Details of the kanji in `prompt`
+        #[serde(default)]
+        kanji: Vec<KanjiInfo>,
     },
 
     /// Broadcast when another player is typing
@@ -101,6 +107,9 @@ pub enum ServerMessage {
     PromptUpdate { 
         new_prompt: String,
         timer_expires_at: Option<u64>,
+        /// Details of the kanji in `new_prompt`
+        #[serde(default)]
+        kanji: Vec<KanjiInfo>,
     },
     PlayerListUpdate { players: Vec<PlayerData> },
     SettingsUpdate { settings: GameSettings },
@@ -108,6 +117,61 @@ pub enum ServerMessage {
     SkipVoteUpdate { votes: usize, required: usize },
     Kicked { player_id: PlayerId },
     ChatMessage(ChatMessage),
+    /// Sent once a time-attack run has been saved for a logged-in player
+    TimeAttackResult { player_id: PlayerId, result: TimeAttackResult },
+    /// Sent once a daily-challenge run has been saved for a logged-in player
+    DailyChallengeResult { player_id: PlayerId, result: DailyChallengeResult },
+    /// Sent to a tournament and all of its match lobbies whenever the bracket changes
+    BracketUpdate { bracket: Bracket },
+    /// A tournament match lobby has been created for these players
+    TournamentMatchReady { lobby_id: LobbyId, player_ids: Vec<PlayerId> },
+    /// The hidden fuse ran out in Bomb mode while `player_id` held the prompt
+    BombExploded { player_id: PlayerId, eliminated: bool },
+    /// Sent when a series game is scored, and with `None` once the series is abandoned
+    SeriesUpdate { series: Option<SeriesStandings> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeAttackResult {
+    pub score: u32,
+    pub personal_best: u32,
+    pub is_new_best: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyChallengeResult {
+    pub score: u32,
+    /// Place on today's leaderboard; `None` when this run wasn't the player's scored attempt.
+    pub rank: Option<u32>,
+    /// Only the first run of the day counts, later ones are practice.
+    pub is_scored: bool,
+}
+
+/// Today's daily-challenge standings, best score first.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyLeaderboard {
+    /// The challenge day as `YYYY-MM-DD` (UTC)
+    pub date: String,
+    pub entries: Vec<DailyLeaderboardEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyLeaderboardEntry {
+    pub rank: u32,
+    pub username: String,
+    pub score: u32,
+}
+
+/// One prompt in a user's spaced-repetition review queue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewQueueEntry {
+    pub prompt: String,
+    /// When the prompt next comes up, as RFC 3339 (UTC)
+    pub due_at: String,
+    pub interval_days: u32,
+    pub repetitions: u32,
+    pub lapses: u32,
+    pub is_due: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -161,6 +225,58 @@ pub struct PlayerData {
     pub is_connected: bool,
     #[serde(default)]
     pub is_spectator: bool,
+    /// Name of the player's team in team Deathmatch, `None` when playing solo
+    #[serde(default)]
+    pub team: Option<String>,
+    /// Computer-controlled opponent added by the leader
+    #[serde(default)]
+    pub is_bot: bool,
+}
+
+/// How a bot opponent plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BotProfile {
+    /// Shortest and longest time the bot thinks before answering, in milliseconds
+    pub min_delay_ms: u32,
+    pub max_delay_ms: u32,
+    /// Percent chance that an answer is correct
+    pub accuracy: u8,
+    /// Percent chance the bot sticks to common JLPT vocabulary when it can
+    pub common_bias: u8,
+}
+
+impl BotProfile {
+    pub fn easy() -> Self {
+        Self { min_delay_ms: 6000, max_delay_ms: 12000, accuracy: 60, common_bias: 90 }
+    }
+
+    pub fn medium() -> Self {
+        Self { min_delay_ms: 3500, max_delay_ms: 8000, accuracy: 80, common_bias: 60 }
+    }
+
+    pub fn hard() -> Self {
+        Self { min_delay_ms: 1500, max_delay_ms: 4000, accuracy: 95, common_bias: 20 }
+    }
+}
+
+impl Default for BotProfile {
+    fn default() -> Self {
+        Self::medium()
+    }
+}
+
+/// Team names with their pooled scores, highest first. Empty when nobody is on a team.
+pub fn team_standings(players: &[PlayerData]) -> Vec<(String, u32)> {
+    let mut totals: Vec<(String, u32)> = Vec::new();
+    for p in players {
+        let Some(team) = &p.team else { continue };
+        match totals.iter_mut().find(|(name, _)| name == team) {
+            Some((_, total)) => *total += p.score,
+            None => totals.push((team.clone(), p.score)),
+        }
+    }
+    totals.sort_by_key(|(_, total)| std::cmp::Reverse(*total));
+    totals
 }
 
 /// Full lobby state, sent to all clients every poll / push.
@@ -172,12 +288,23 @@ pub struct LobbyInfo {
     pub players: Vec<PlayerData>,
     pub settings: GameSettings,
     pub status: GameStatus,
+    #[serde(default)]
+    pub tier: Option<String>,
+    /// The tournament this lobby hosts or is a match of
+    #[serde(default)]
+    pub bracket: Option<Bracket>,
+    /// Game wins so far when the lobby is playing a best-of-N series
+    #[serde(default)]
+    pub series: Option<SeriesStandings>,
+    /// Content modes the server has the data for; empty until the server has said
+    #[serde(default)]
+    pub content_modes: Vec<ContentMode>,
 }
 
 /// Per-game tunables chosen by the leader.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GameSettings {
-    pub difficulty_levels: Vec<String>,
+    pub difficulty_levels: Vec<DifficultySet>,
     pub time_limit_seconds: Option<u32>,
     pub max_players: u32,
     pub weighted: bool,
@@ -187,6 +314,212 @@ pub struct GameSettings {
     pub initial_lives: Option<u32>,
     pub duel_allow_kanji_reuse: bool,
     pub is_public: bool,
+    /// Deathmatch teams; empty means every player plays for themselves.
+    #[serde(default)]
+    pub teams: Vec<Team>,
+    #[serde(default)]
+    pub reading_type: ReadingType,
+    /// How forgiving answer checking is about the way kana answers are typed
+    #[serde(default)]
+    pub answer_strictness: AnswerStrictness,
+    /// Whether Vocab answers may be typed in romaji instead of kana
+    #[serde(default)]
+    pub allow_romaji: bool,
+    /// Whether Kanji answers must be dictionary headwords, rather than also accepting inflected forms like 食べた
+    #[serde(default)]
+    pub exact_headwords: bool,
+    /// Whether archaic, rare and non-Japanese headwords the dictionary filters out still count as words
+    #[serde(default)]
+    pub full_dictionary: bool,
+    /// Correct answers in a row needed to climb a level in Survival
+    #[serde(default)]
+    pub survival_streak: Option<u32>,
+    /// Shortest and longest the hidden fuse can burn in Bomb mode
+    #[serde(default)]
+    pub fuse_min_seconds: Option<u32>,
+    #[serde(default)]
+    pub fuse_max_seconds: Option<u32>,
+    /// Games in a best-of-N series; `None` or 1 plays single games
+    #[serde(default)]
+    pub series_length: Option<u32>,
+}
+
+/// A named group of players whose scores are pooled in Deathmatch.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Team {
+    pub name: String,
+    pub members: Vec<PlayerId>,
+}
+
+/// Game wins per player across a best-of-N series.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeriesStandings {
+    pub best_of: u32,
+    pub games_played: u32,
+    /// Players who have won at least one game, in the order they first won
+    pub wins: Vec<SeriesEntry>,
+    /// Set once a player has won more than half of the games, or has the most wins after `best_of` games
+    pub champion: Option<PlayerId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeriesEntry {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub wins: u32,
+}
+
+impl SeriesStandings {
+    pub fn new(best_of: u32) -> Self {
+        Self { best_of, ..Default::default() }
+    }
+
+    pub fn wins_needed(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    pub fn wins_of(&self, player_id: &PlayerId) -> u32 {
+        self.wins.iter().find(|e| &e.player_id == player_id).map_or(0, |e| e.wins)
+    }
+
+    /// Score one finished game for `player_id`, crowning them if that clinches the series.
+    /// With three or more players nobody may reach a majority, so after `best_of` games the most wins take it:
+    /// a tie goes to the winner of the last game if they're in it, otherwise to whoever of them won a game first.
+    pub fn record_win(&mut self, player_id: PlayerId, name: String) {
+        self.games_played += 1;
+        let entry = match self.wins.iter_mut().position(|e| e.player_id == player_id) {
+            Some(idx) => &mut self.wins[idx],
+            None => {
+                self.wins.push(SeriesEntry { player_id: player_id.clone(), name, wins: 0 });
+                self.wins.last_mut().expect("just pushed")
+            }
+        };
+        entry.wins += 1;
+        if entry.wins >= self.wins_needed() {
+            self.champion = Some(player_id);
+        } else if self.games_played >= self.best_of {
+            let most = self.wins.iter().map(|e| e.wins).max().unwrap_or(0);
+            let champion = if self.wins_of(&player_id) == most {
+                player_id
+            } else {
+                self.wins.iter().find(|e| e.wins == most).map(|e| e.player_id.clone()).unwrap_or(player_id)
+            };
+            self.champion = Some(champion);
+        }
+    }
+}
+
+/// A single-elimination bracket of Duel matches.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bracket {
+    /// The lobby the tournament was started from
+    pub tournament_id: LobbyId,
+    /// First round first; each round has half as many matches as the one before
+    pub rounds: Vec<Vec<BracketMatch>>,
+    /// Set when a match is abandoned before it produces a winner
+    pub cancelled: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BracketMatch {
+    /// An empty slot is a bye in the first round and a winner still to be decided after that
+    pub players: [Option<BracketEntrant>; 2],
+    /// The lobby the match is played in, once it has started
+    pub lobby_id: Option<LobbyId>,
+    pub winner: Option<PlayerId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BracketEntrant {
+    pub id: PlayerId,
+    pub name: String,
+}
+
+impl Bracket {
+    pub fn champion(&self) -> Option<&BracketEntrant> {
+        self.rounds.last()?.first()?.winning_entrant()
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.cancelled && self.champion().is_none()
+    }
+
+    /// The undecided match `player_id` is currently playing in, if any.
+    pub fn active_match_of(&self, player_id: &PlayerId) -> Option<&BracketMatch> {
+        self.rounds.iter().flatten().find(|m| {
+            m.winner.is_none() && m.lobby_id.is_some() && m.players.iter().flatten().any(|e| &e.id == player_id)
+        })
+    }
+}
+
+impl BracketMatch {
+    pub fn winning_entrant(&self) -> Option<&BracketEntrant> {
+        let winner = self.winner.as_ref()?;
+        self.players.iter().flatten().find(|e| &e.id == winner)
+    }
+}
+
+/// Length of a time-attack run when the leader hasn't set a time limit.
+pub const DEFAULT_TIME_ATTACK_SECONDS: u32 = 60;
+pub const DEFAULT_FUSE_MIN_SECONDS: u32 = 10;
+pub const DEFAULT_FUSE_MAX_SECONDS: u32 = 30;
+
+impl GameSettings {
+    /// Seconds on the countdown clock. Per prompt in most modes, for the whole run in timed runs.
+    pub fn timer_seconds(&self) -> Option<u32> {
+        match self.mode {
+            GameMode::TimeAttack => Some(self.time_limit_seconds.unwrap_or(DEFAULT_TIME_ATTACK_SECONDS)),
+            GameMode::Daily => Some(DEFAULT_TIME_ATTACK_SECONDS),
+            _ => self.time_limit_seconds,
+        }
+    }
+
+    /// Inclusive range of seconds the Bomb fuse may burn for, always at least one second long.
+    pub fn fuse_range(&self) -> (u32, u32) {
+        let min = self.fuse_min_seconds.unwrap_or(DEFAULT_FUSE_MIN_SECONDS).max(1);
+        let max = self.fuse_max_seconds.unwrap_or(DEFAULT_FUSE_MAX_SECONDS).max(min);
+        (min, max)
+    }
+
+    /// Whether games are grouped into a best-of-N series.
+    /// Only head-to-head modes with a single winner per game can be played as a series.
+    pub fn is_series(&self) -> bool {
+        self.series_length.is_some_and(|n| n > 1)
+            && !self.is_team_game()
+            && (self.mode == GameMode::Deathmatch || self.mode.is_turn_based())
+    }
+
+    /// These settings with the fixed daily-challenge rules applied, so every run on a day is comparable.
+    /// What counts as a correct answer is pinned too, or a lenient lobby would post higher scores.
+    pub fn daily_challenge(&self) -> Self {
+        let defaults = Self::default();
+        Self {
+            difficulty_levels: defaults.difficulty_levels,
+            time_limit_seconds: None,
+            weighted: false,
+            mode: GameMode::Daily,
+            content_mode: ContentMode::Kanji,
+            teams: Vec::new(),
+            reading_type: defaults.reading_type,
+            answer_strictness: defaults.answer_strictness,
+            allow_romaji: defaults.allow_romaji,
+            exact_headwords: defaults.exact_headwords,
+            full_dictionary: defaults.full_dictionary,
+            ..self.clone()
+        }
+    }
+
+    pub fn is_team_game(&self) -> bool {
+        self.mode == GameMode::Deathmatch && !self.teams.is_empty()
+    }
+
+    /// The team `player_id` plays for, if this is a team game and they have been assigned one.
+    pub fn team_of(&self, player_id: &PlayerId) -> Option<&Team> {
+        if !self.is_team_game() {
+            return None;
+        }
+        self.teams.iter().find(|t| t.members.contains(player_id))
+    }
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -195,6 +528,31 @@ pub enum GameMode {
     Deathmatch,
     Duel,
     Zen,
+    Shiritori,
+    TimeAttack,
+    Survival,
+    Daily,
+    Tournament,
+    Bomb,
+    Review,
+}
+
+impl GameMode {
+    /// Modes where players answer one at a time from `turn_order` and lose lives.
+    /// Bomb only costs a life when the fuse runs out, the others on a miss.
+    pub fn is_turn_based(&self) -> bool {
+        matches!(self, Self::Duel | Self::Shiritori | Self::Survival | Self::Bomb)
+    }
+
+    /// Solo modes played against a single countdown for the whole run.
+    pub fn is_timed_run(&self) -> bool {
+        matches!(self, Self::TimeAttack | Self::Daily)
+    }
+
+    /// Modes whose results are kept per account, so only one person may play them at a time.
+    pub fn is_solo(&self) -> bool {
+        self.is_timed_run() || *self == Self::Review
+    }
 }
 
 
@@ -202,11 +560,11 @@ impl Default for GameSettings {
     fn default() -> Self {
         Self {
             difficulty_levels: vec![ // Replaced alloc::vec!
-                "N1".into(),
-                "N2".into(),
-                "N3".into(),
-                "N4".into(),
-                "N5".into(),
+                DifficultySet::Jlpt(1),
+                DifficultySet::Jlpt(2),
+                DifficultySet::Jlpt(3),
+                DifficultySet::Jlpt(4),
+                DifficultySet::Jlpt(5),
             ],
             time_limit_seconds: None,
             max_players: 4,
@@ -217,6 +575,16 @@ impl Default for GameSettings {
             initial_lives: Some(3), // Default lives for Duel
             duel_allow_kanji_reuse: false,
             is_public: false,
+            teams: Vec::new(),
+            reading_type: ReadingType::Any,
+            answer_strictness: AnswerStrictness::Standard,
+            allow_romaji: false,
+            exact_headwords: false,
+            full_dictionary: false,
+            survival_streak: Some(5),
+            fuse_min_seconds: Some(DEFAULT_FUSE_MIN_SECONDS),
+            fuse_max_seconds: Some(DEFAULT_FUSE_MAX_SECONDS),
+            series_length: None,
         }
     }
 }
@@ -252,17 +620,221 @@ pub struct CheckWordResponse {
     pub error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_details: Option<Vec<String>>,
+    /// The dictionary entry a correct answer matched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted: Option<WordInfo>,
+    /// Words that would have been accepted, most common first, after a miss
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<WordInfo>>,
+    /// Details of the kanji in the prompt that was just missed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missed_kanji: Vec<KanjiInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt: Option<String>,
+    /// Details of the kanji in `prompt`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prompt_kanji: Vec<KanjiInfo>,
     pub timer_expires_at: Option<u64>,
 }
 
 
+/// What KANJIDIC2 knows about a kanji, shown alongside kanji prompts.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KanjiInfo {
+    pub character: String,
+    pub meanings: Vec<String>,
+    /// In katakana, as KANJIDIC writes them
+    pub on_readings: Vec<String>,
+    /// In hiragana, with okurigana after a `.`
+    pub kun_readings: Vec<String>,
+    pub stroke_count: Option<u8>,
+    /// 1 to 6 for the Kyōiku grades, 8 for the rest of the Jōyō list and 9 or 10 for Jinmeiyō kanji
+    pub grade: Option<u8>,
+    /// Classical (Kangxi) radical number, 1 to 214
+    pub radical: Option<u8>,
+}
+
+impl KanjiInfo {
+    /// Whether KANJIDIC gave anything beyond the character itself
+    pub fn has_details(&self) -> bool {
+        !self.meanings.is_empty() || !self.on_readings.is_empty() || !self.kun_readings.is_empty()
+            || !self.facts().is_empty()
+    }
+
+    /// One line of facts, e.g. "4 strokes · grade 1 · radical 72"
+    pub fn facts(&self) -> String {
+        let mut facts = Vec::new();
+        if let Some(strokes) = self.stroke_count {
+            facts.push(format!("{} strokes", strokes));
+        }
+        match self.grade {
+            Some(grade @ 1..=6) => facts.push(format!("grade {}", grade)),
+            Some(8) => facts.push("Jōyō".to_string()),
+            Some(9 | 10) => facts.push("Jinmeiyō".to_string()),
+            _ => {},
+        }
+        if let Some(radical) = self.radical {
+            facts.push(format!("radical {}", radical));
+        }
+        facts.join(" · ")
+    }
+}
+
+/// What the dictionary knows about a word, shown as answer feedback.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WordInfo {
+    pub word: String,
+    pub readings: Vec<String>,
+    /// 1 is the most common word
+    pub frequency_rank: Option<u32>,
+    /// 1 for N1 through 5 for N5
+    pub jlpt_level: Option<u8>,
+}
+
+impl WordInfo {
+    /// The word with its readings and rank, e.g. `日本 (にほん・にっぽん, N5, #42)`.
+    pub fn summary(&self) -> String {
+        let mut notes = Vec::new();
+        if !self.readings.is_empty() {
+            notes.push(self.readings.join("・"));
+        }
+        if let Some(level) = self.jlpt_level {
+            notes.push(format!("N{}", level));
+        }
+        if let Some(rank) = self.frequency_rank {
+            notes.push(format!("#{}", rank));
+        }
+        if notes.is_empty() {
+            self.word.clone()
+        } else {
+            format!("{} ({})", self.word, notes.join(", "))
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ContentMode {
     #[default]
     Kanji,
     Vocab,
+    ReverseVocab,
+    Meaning,
+    Compound,
+    KanjiReading,
+}
+
+impl ContentMode {
+    pub const ALL: [ContentMode; 6] = [
+        Self::Kanji, Self::Vocab, Self::ReverseVocab, Self::Meaning, Self::Compound, Self::KanjiReading,
+    ];
+
+    /// Modes whose prompts come from the JLPT word lists rather than from kanji.
+    pub fn uses_words(&self) -> bool {
+        matches!(self, Self::Vocab | Self::ReverseVocab | Self::Meaning)
+    }
+}
+
+/// A set of kanji a lobby can draw prompts from; lobbies may combine any of them.
+/// Sent and stored by id (`N5`, `Joyo`, `G1`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum DifficultySet {
+    /// JLPT level, from 1 (N1, the hardest) to 5 (N5)
+    Jlpt(u8),
+    /// The full Jōyō list
+    Joyo,
+    /// The Kyōiku kanji taught in school grade 1 to 6
+    Grade(u8),
+}
+
+impl DifficultySet {
+    /// Every set, in the order the server keeps their kanji.
+    pub const ALL: [DifficultySet; 12] = [
+        Self::Jlpt(1), Self::Jlpt(2), Self::Jlpt(3), Self::Jlpt(4), Self::Jlpt(5),
+        Self::Joyo,
+        Self::Grade(1), Self::Grade(2), Self::Grade(3), Self::Grade(4), Self::Grade(5), Self::Grade(6),
+    ];
+
+    /// Position in `ALL`, or `None` for a level or grade that doesn't exist.
+    pub fn index(self) -> Option<usize> {
+        Self::ALL.iter().position(|s| *s == self)
+    }
+
+    /// Name shown to players, e.g. `N5`, `Jōyō` or `Grade 1`.
+    pub fn label(self) -> String {
+        match self {
+            Self::Jlpt(level) => format!("N{}", level),
+            Self::Joyo => "Jōyō".to_string(),
+            Self::Grade(grade) => format!("Grade {}", grade),
+        }
+    }
+
+    /// Whether the set has JLPT vocabulary for the word-based content modes.
+    pub fn has_words(self) -> bool {
+        matches!(self, Self::Jlpt(_))
+    }
+}
+
+impl core::fmt::Display for DifficultySet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Jlpt(level) => write!(f, "N{}", level),
+            Self::Joyo => write!(f, "Joyo"),
+            Self::Grade(grade) => write!(f, "G{}", grade),
+        }
+    }
+}
+
+impl core::str::FromStr for DifficultySet {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let set = match s {
+            "Joyo" => Self::Joyo,
+            _ => {
+                let number = |rest: &str| rest.parse::<u8>().ok();
+                match (s.strip_prefix('N').and_then(number), s.strip_prefix('G').and_then(number)) {
+                    (Some(level), _) => Self::Jlpt(level),
+                    (_, Some(grade)) => Self::Grade(grade),
+                    _ => return Err(format!("Unknown difficulty set {}", s)),
+                }
+            },
+        };
+        set.index().map(|_| set).ok_or_else(|| format!("Unknown difficulty set {}", s))
+    }
+}
+
+impl TryFrom<String> for DifficultySet {
+    type Error = String;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<DifficultySet> for String {
+    fn from(set: DifficultySet) -> Self {
+        set.to_string()
+    }
+}
+
+/// Which readings count in the kanji-reading quiz.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReadingType {
+    #[default]
+    Any,
+    Onyomi,
+    Kunyomi,
+}
+
+/// How closely a kana answer has to match the expected reading.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnswerStrictness {
+    /// The answer must match character for character
+    Strict,
+    /// Katakana, half-width and full-width forms and surrounding punctuation are ignored
+    #[default]
+    Standard,
+    /// As Standard, and a long-vowel mark (ー) also counts as the vowel it stretches
+    Lenient,
 }
 
 /// The current game's prompt - varies by content mode
@@ -270,15 +842,31 @@ pub enum ContentMode {
 pub enum ActivePrompt {
     /// Player must sumbit a word containing this kanji character
     Kanji { character: String },
+    /// Player must submit a word containing every one of these kanji
+    Compound { characters: Vec<String> },
+    /// Player must submit one of this kanji's readings
+    KanjiReading { character: String, readings: Vec<String> },
     /// Player must submit the correct hiragana reading of this word
     Vocab { word: String, readings: Vec<String> },
+    /// Player must submit any kanji spelling of this reading
+    ReverseVocab { reading: String, words: Vec<String> },
+    /// Player must submit `word`, in kanji or any of its kana readings, given its English meaning
+    Meaning { gloss: String, word: String, readings: Vec<String> },
+    /// Player must submit a word whose reading starts with the last kana of `reading`
+    Shiritori { word: String, reading: String },
 }
 
 impl ActivePrompt {
-    pub fn display_text(&self) -> &str {
+    pub fn display_text(&self) -> String {
         match self {
-            Self::Kanji { character } => character,
-            Self::Vocab { word, .. } => word,
+            Self::Kanji { character } => character.clone(),
+            Self::Compound { characters } => characters.join(" + "),
+            Self::KanjiReading { character, .. } => character.clone(),
+            Self::Vocab { word, .. } => word.clone(),
+            Self::ReverseVocab { reading, .. } => reading.clone(),
+            Self::Meaning { gloss, .. } => gloss.clone(),
+            Self::Shiritori { word, reading } if word == reading => word.clone(),
+            Self::Shiritori { word, reading } => format!("{}（{}）", word, reading),
         }
     }
 }